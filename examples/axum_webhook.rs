@@ -0,0 +1,52 @@
+//! Axum Webhook
+//! ============
+//!
+//! Reference: <https://razorpay.com/docs/webhooks/validate-test>
+//!
+//! This example shows how to receive verified webhooks in an axum handler.
+//!
+//! Run it with `cargo run --example axum_webhook --features axum`.
+
+use std::{env, time::Duration};
+
+use axum::{extract::FromRef, http::StatusCode, routing::post, Router};
+use rusty_razorpay::webhook_extract::{VerifiedWebhook, WebhookVerifier};
+
+#[derive(Clone)]
+struct AppState {
+    verifier: WebhookVerifier,
+}
+
+impl FromRef<AppState> for WebhookVerifier {
+    fn from_ref(state: &AppState) -> Self {
+        state.verifier.clone()
+    }
+}
+
+async fn webhook(webhook: VerifiedWebhook) -> StatusCode {
+    println!(
+        "webhook received: {:?} ({:?})",
+        webhook.event.type_, webhook.event_id
+    );
+
+    StatusCode::OK
+}
+
+#[tokio::main]
+async fn main() {
+    let webhook_secret = env::var("RAZORPAY_WEBHOOK_SECRET")
+        .expect("Missing RAZORPAY_WEBHOOK_SECRET in env");
+    let state = AppState {
+        verifier: WebhookVerifier::new(webhook_secret)
+            .max_age(Duration::from_secs(5 * 60)),
+    };
+
+    let app = Router::new()
+        .route("/webhook", post(webhook))
+        .with_state(state);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+
+    axum::serve(listener, app).await.unwrap();
+}
@@ -716,6 +716,7 @@ impl Account {
             .post(RequestParams {
                 url: "/accounts".to_owned(),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -735,6 +736,7 @@ impl Account {
             .get(RequestParams {
                 url: format!("/accounts/{}", account_id),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -755,6 +757,7 @@ impl Account {
             .post(RequestParams {
                 url: format!("/accounts/{}", account_id),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -774,6 +777,7 @@ impl Account {
             .delete(RequestParams {
                 url: format!("/accounts/{}", account_id),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
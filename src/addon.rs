@@ -2,6 +2,7 @@
 use alloc::{borrow::ToOwned, format, string::String};
 
 use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -11,6 +12,7 @@ use crate::{
     error::{InternalApiResult, RazorpayResult},
     ids::{AddonId, SubscriptionId},
     item::{CreateItem, Item},
+    pagination::paginate,
     InvoiceId, Razorpay,
 };
 
@@ -52,6 +54,7 @@ impl Addon {
             .post(RequestParams {
                 url: format!("/subscriptions/{}/addons", subscription_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -74,6 +77,7 @@ impl Addon {
             .get(RequestParams {
                 url: "/addons".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -84,6 +88,16 @@ impl Addon {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Addon>> + '_
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| Addon::list(razorpay, filter))
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         addon_id: &AddonId,
@@ -93,6 +107,7 @@ impl Addon {
             .get(RequestParams {
                 url: format!("/addons/{}", addon_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -112,6 +127,7 @@ impl Addon {
             .delete(RequestParams {
                 url: format!("/addons/{}", addon_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -3,11 +3,28 @@ use alloc::{
     borrow::ToOwned, format, string::String, string::ToString, vec, vec::Vec,
 };
 
-use reqwest::{header::HeaderMap, Client, ClientBuilder};
-use serde::{de::DeserializeOwned, ser::Error, Serialize};
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, RETRY_AFTER},
+    multipart::{Form, Part},
+    Client, Method, Request, RequestBuilder, Response,
+};
+use serde::{de::DeserializeOwned, ser::Error, Deserialize, Serialize};
 use serde_json::{to_value, Value};
 
-use crate::error::RazorpayResult;
+use crate::{
+    error::{ApiError, RazorpayError, RazorpayResult},
+    retry::{
+        IdempotencyKey, RetryPolicy, IDEMPOTENCY_KEY_HEADER,
+        PAYOUT_IDEMPOTENCY_HEADER,
+    },
+    util::generate_idempotency_key,
+};
 
 #[derive(Debug)]
 pub struct Api {
@@ -15,22 +32,34 @@ pub struct Api {
     key_secret: String,
     base_url: String,
     client: Client,
+    default_headers: HeaderMap,
     version: String,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) auto_idempotency_keys: bool,
 }
 
 pub struct ApiOptions {
-    pub user_agent: String,
     pub base_url: String,
     pub key_id: String,
     pub key_secret: String,
+    pub client: Client,
+    pub default_headers: HeaderMap,
+    pub retry_policy: RetryPolicy,
+    pub auto_idempotency_keys: bool,
 }
 
 pub struct RequestParams<T: Serialize = ()> {
     pub url: String,
     pub version: Option<String>,
+    pub idempotency_key: Option<IdempotencyKey>,
     pub data: Option<T>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ApiError,
+}
+
 fn make_serializable<T>(value: &T) -> serde_json::Result<Vec<(String, String)>>
 where
     T: Serialize,
@@ -83,20 +112,33 @@ where
     Ok(records)
 }
 
+// `Retry-After` is either a number of seconds or an HTTP date
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
 impl Api {
     pub fn new(options: ApiOptions) -> Self {
-        let mut default_headers = HeaderMap::new();
-        default_headers
-            .insert("User-Agent", options.user_agent.parse().unwrap());
         Self {
             key_id: options.key_id,
             key_secret: options.key_secret,
             base_url: options.base_url,
-            client: ClientBuilder::new()
-                .default_headers(default_headers)
-                .build()
-                .unwrap(),
+            client: options.client,
+            default_headers: options.default_headers,
             version: "v1".to_owned(),
+            retry_policy: options.retry_policy,
+            auto_idempotency_keys: options.auto_idempotency_keys,
         }
     }
 
@@ -112,37 +154,138 @@ impl Api {
         )
     }
 
-    pub async fn get<T, R>(&self, params: RequestParams<T>) -> RazorpayResult<R>
+    async fn send<R>(&self, req: RequestBuilder) -> RazorpayResult<R>
     where
-        T: Serialize,
         R: DeserializeOwned,
     {
-        let res = self
+        Ok(self.send_raw(req).await?.json().await?)
+    }
+
+    async fn send_raw(&self, req: RequestBuilder) -> RazorpayResult<Response> {
+        let mut req = req.build()?;
+
+        // a generated key is part of the built request, so every retry
+        // below sends the same one
+        if self.auto_idempotency_keys
+            && matches!(*req.method(), Method::POST | Method::PATCH)
+            && !req.headers().contains_key(IDEMPOTENCY_KEY_HEADER)
+            && !req.headers().contains_key(PAYOUT_IDEMPOTENCY_HEADER)
+        {
+            if let Ok(key) = HeaderValue::from_str(&generate_idempotency_key())
+            {
+                req.headers_mut().insert(IDEMPOTENCY_KEY_HEADER, key);
+            }
+        }
+
+        self.execute_with_retries(req).await
+    }
+
+    #[cfg(feature = "std")]
+    async fn execute_with_retries(
+        &self,
+        mut req: Request,
+    ) -> RazorpayResult<Response> {
+        let can_retry = self.retry_policy.can_retry(&req);
+        let mut attempt = 1;
+
+        loop {
+            let next = if can_retry { req.try_clone() } else { None };
+
+            let error = match self.execute(req).await {
+                Ok(res) => return Ok(res),
+                Err(error) => error,
+            };
+
+            match (next, self.retry_policy.delay(attempt, &error)) {
+                (Some(next), Some(delay)) => {
+                    tokio::time::sleep(delay).await;
+                    req = next;
+                    attempt += 1;
+                }
+                _ => return Err(error),
+            }
+        }
+    }
+
+    // waiting between attempts needs the tokio timer, without `std` every
+    // request is sent once
+    #[cfg(not(feature = "std"))]
+    async fn execute_with_retries(
+        &self,
+        req: Request,
+    ) -> RazorpayResult<Response> {
+        self.execute(req).await
+    }
+
+    async fn execute(&self, req: Request) -> RazorpayResult<Response> {
+        let res = self.client.execute(req).await?;
+        let status = res.status();
+
+        if status.is_success() {
+            return Ok(res);
+        }
+
+        let retry_after = parse_retry_after(res.headers());
+
+        // Razorpay wraps its errors as `{ "error": { ... } }`, anything else
+        // (e.g. an HTML page from a proxy or the gateway) is kept verbatim
+        let body = res.text().await?;
+        Err(match serde_json::from_str::<ErrorBody>(&body) {
+            Ok(ErrorBody { mut error }) => {
+                error.status = Some(status);
+                error.retry_after = retry_after;
+                RazorpayError::ApiError(error)
+            }
+            Err(_) => RazorpayError::HttpError {
+                status,
+                body,
+                retry_after,
+            },
+        })
+    }
+
+    fn request<T: Serialize>(
+        &self,
+        method: Method,
+        params: &RequestParams<T>,
+    ) -> RequestBuilder {
+        let req = self
             .client
-            .get(self.get_entity_url(&params))
+            .request(method, self.get_entity_url(params))
+            .headers(self.default_headers.clone())
             .basic_auth(&self.key_id, Some(&self.key_secret));
 
+        match &params.idempotency_key {
+            Some(key) => req.header(key.header(), key.value()),
+            None => req,
+        }
+    }
+
+    pub async fn get<T, R>(&self, params: RequestParams<T>) -> RazorpayResult<R>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        let res = self.request(Method::GET, &params);
+
         let res = if let Some(data) = params.data {
             res.query(&make_serializable(&data)?)
         } else {
             res
         };
 
-        Ok(res.send().await?.error_for_status()?.json().await?)
+        self.send(res).await
     }
 
     pub async fn post<T, R>(
         &self,
         params: RequestParams<T>,
-    ) -> reqwest::Result<R>
+    ) -> RazorpayResult<R>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let res = self
-            .client
-            .post(self.get_entity_url(&params))
-            .basic_auth(&self.key_id, Some(&self.key_secret));
+        let res = self.request(Method::POST, &params);
 
         let res = if let Some(data) = params.data {
             res.json(&data)
@@ -150,21 +293,15 @@ impl Api {
             res
         };
 
-        res.send().await?.error_for_status()?.json().await
+        self.send(res).await
     }
 
-    pub async fn put<T, R>(
-        &self,
-        params: RequestParams<T>,
-    ) -> reqwest::Result<R>
+    pub async fn put<T, R>(&self, params: RequestParams<T>) -> RazorpayResult<R>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let res = self
-            .client
-            .put(self.get_entity_url(&params))
-            .basic_auth(&self.key_id, Some(&self.key_secret));
+        let res = self.request(Method::PUT, &params);
 
         let res = if let Some(data) = params.data {
             res.json(&data)
@@ -172,21 +309,18 @@ impl Api {
             res
         };
 
-        res.send().await?.error_for_status()?.json().await
+        self.send(res).await
     }
 
     pub async fn patch<T, R>(
         &self,
         params: RequestParams<T>,
-    ) -> reqwest::Result<R>
+    ) -> RazorpayResult<R>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let res = self
-            .client
-            .patch(self.get_entity_url(&params))
-            .basic_auth(&self.key_id, Some(&self.key_secret));
+        let res = self.request(Method::PATCH, &params);
 
         let res = if let Some(data) = params.data {
             res.json(&data)
@@ -194,40 +328,92 @@ impl Api {
             res
         };
 
-        res.send().await?.error_for_status()?.json().await
+        self.send(res).await
     }
 
     pub async fn delete<T, R>(
         &self,
         params: RequestParams<T>,
-    ) -> reqwest::Result<R>
+    ) -> RazorpayResult<R>
+    where
+        T: Serialize,
+        R: DeserializeOwned,
+    {
+        let res = self.request(Method::DELETE, &params);
+
+        self.send(res).await
+    }
+
+    /// Sends a GET request and returns the successful response as is, for
+    /// endpoints that don't respond with JSON.
+    pub async fn get_raw<T>(
+        &self,
+        params: RequestParams<T>,
+    ) -> RazorpayResult<Response>
+    where
+        T: Serialize,
+    {
+        let res = self.request(Method::GET, &params);
+
+        let res = if let Some(data) = params.data {
+            res.query(&make_serializable(&data)?)
+        } else {
+            res
+        };
+
+        self.send_raw(res).await
+    }
+
+    /// Sends a DELETE request and returns the successful response as is, for
+    /// endpoints answering with an empty body, e.g. `204 No Content`.
+    pub async fn delete_raw<T>(
+        &self,
+        params: RequestParams<T>,
+    ) -> RazorpayResult<Response>
+    where
+        T: Serialize,
+    {
+        let res = self.request(Method::DELETE, &params);
+
+        self.send_raw(res).await
+    }
+
+    /// Sends the data as the text fields of a `multipart/form-data` body
+    /// along with `file` as the field `file_field`.
+    pub async fn post_multipart<T, R>(
+        &self,
+        params: RequestParams<T>,
+        file_field: &'static str,
+        file: Part,
+    ) -> RazorpayResult<R>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        self.client
-            .delete(self.get_entity_url(&params))
-            .basic_auth(&self.key_id, Some(&self.key_secret))
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await
+        let mut form = Form::new();
+
+        if let Some(data) = &params.data {
+            for (name, value) in make_serializable(data)? {
+                form = form.text(name, value);
+            }
+        }
+        form = form.part(file_field, file);
+
+        let res = self.request(Method::POST, &params).multipart(form);
+
+        self.send(res).await
     }
 
     #[allow(dead_code)]
     pub async fn post_form_data<T, R>(
         &self,
         params: RequestParams<T>,
-    ) -> reqwest::Result<R>
+    ) -> RazorpayResult<R>
     where
         T: Serialize,
         R: DeserializeOwned,
     {
-        let res = self
-            .client
-            .post(self.get_entity_url(&params))
-            .basic_auth(&self.key_id, Some(&self.key_secret));
+        let res = self.request(Method::POST, &params);
 
         let res = if let Some(data) = params.data {
             res.form(&data)
@@ -235,6 +421,6 @@ impl Api {
             res
         };
 
-        res.send().await?.error_for_status()?.json().await
+        self.send(res).await
     }
 }
@@ -84,6 +84,7 @@ impl Card {
             .get(RequestParams {
                 url: format!("/cards/{}", card_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -0,0 +1,217 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+
+use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::ContactId,
+    pagination::paginate,
+    util::deserialize_notes,
+    BatchId, Razorpay,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactType {
+    Vendor,
+    Customer,
+    Employee,
+    #[serde(rename = "self")]
+    Self_,
+}
+
+/// A RazorpayX contact, the person or business payouts are made to.
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(tag = "entity", rename = "contact")]
+pub struct Contact {
+    pub id: ContactId,
+    pub name: String,
+    pub contact: Option<String>,
+    pub email: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<ContactType>,
+    pub reference_id: Option<String>,
+    pub batch_id: Option<BatchId>,
+    pub active: bool,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct CreateContact<'a> {
+    pub name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<&'a str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ContactType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct UpdateContact<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<&'a str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ContactType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct ListContacts<'a> {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ContactType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+impl Contact {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateContact<'_>,
+    ) -> RazorpayResult<Contact> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/contacts".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(contact) => Ok(contact),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        contact_id: &ContactId,
+    ) -> RazorpayResult<Contact> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/contacts/{}", contact_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(contact) => Ok(contact),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: Option<ListContacts<'_>>,
+    ) -> RazorpayResult<Collection<Contact>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/contacts".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(contacts) => Ok(contacts),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListContacts<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<Contact>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Contact::list(
+                razorpay,
+                Some(ListContacts {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
+    pub async fn update(
+        razorpay: &Razorpay,
+        contact_id: &ContactId,
+        params: UpdateContact<'_>,
+    ) -> RazorpayResult<Contact> {
+        let res = razorpay
+            .api
+            .patch(RequestParams {
+                url: format!("/contacts/{}", contact_id),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(contact) => Ok(contact),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Activates or deactivates a contact, payouts can't be made to
+    /// deactivated contacts.
+    pub async fn set_active(
+        razorpay: &Razorpay,
+        contact_id: &ContactId,
+        active: bool,
+    ) -> RazorpayResult<Contact> {
+        let res = razorpay
+            .api
+            .patch(RequestParams {
+                url: format!("/contacts/{}", contact_id),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "active": active })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(contact) => Ok(contact),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
@@ -2,13 +2,15 @@
 use alloc::{borrow::ToOwned, format, string::String};
 
 use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     api::RequestParams,
-    common::{Collection, Object},
+    common::{Collection, Filter, Object},
     error::{InternalApiResult, RazorpayResult},
     ids::CustomerId,
+    pagination::paginate,
     util::{deserialize_notes, serialize_bool_as_int_option},
     Razorpay,
 };
@@ -72,6 +74,7 @@ impl Customer {
             .post(RequestParams {
                 url: "/customers".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -92,6 +95,7 @@ impl Customer {
             .put(RequestParams {
                 url: format!("/customers/{}", customer_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -114,6 +118,7 @@ impl Customer {
             .get(RequestParams {
                 url: "/customers".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -124,6 +129,30 @@ impl Customer {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Customer>> + '_
+    where
+        T: Into<Option<ListCustomers>>,
+    {
+        let filter = params.into().map(|params| Filter {
+            count: params.count.map(usize::from),
+            skip: params.skip.map(|skip| skip as usize),
+            ..Default::default()
+        });
+
+        paginate(filter, move |filter| {
+            Customer::list(
+                razorpay,
+                ListCustomers {
+                    count: filter.count.and_then(|count| count.try_into().ok()),
+                    skip: filter.skip.map(|skip| skip as u64),
+                },
+            )
+        })
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         customer_id: &CustomerId,
@@ -133,6 +162,7 @@ impl Customer {
             .get(RequestParams {
                 url: format!("/customers/{}", customer_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -5,17 +5,19 @@ use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     api::RequestParams,
-    common::{Collection, Currency},
+    common::{Collection, Currency, Filter},
     error::{InternalApiResult, RazorpayResult},
     ids::DisputeId,
+    pagination::paginate,
     PaymentId, Razorpay,
 };
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum DisputeStatus {
     Open,
@@ -25,7 +27,7 @@ pub enum DisputeStatus {
     Closed,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum DisputePhase {
     Fraud,
@@ -114,15 +116,20 @@ pub struct ContestDispute<'a> {
 }
 
 impl Dispute {
-    pub async fn list(
+    pub async fn list<T>(
         razorpay: &Razorpay,
-    ) -> RazorpayResult<Collection<Dispute>> {
+        params: T,
+    ) -> RazorpayResult<Collection<Dispute>>
+    where
+        T: Into<Option<Filter>>,
+    {
         let res = razorpay
             .api
             .get(RequestParams {
                 url: "/disputes".to_owned(),
                 version: None,
-                data: None::<()>,
+                idempotency_key: None,
+                data: params.into(),
             })
             .await?;
 
@@ -132,6 +139,16 @@ impl Dispute {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Dispute>> + '_
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| Dispute::list(razorpay, filter))
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         dispute_id: &DisputeId,
@@ -141,6 +158,7 @@ impl Dispute {
             .get(RequestParams {
                 url: format!("/disputes/{}", dispute_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -160,6 +178,7 @@ impl Dispute {
             .post(RequestParams {
                 url: format!("/disputes/{}/accept", dispute_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -180,6 +199,7 @@ impl Dispute {
             .patch(RequestParams {
                 url: format!("/disputes/{}/contest", dispute_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -0,0 +1,432 @@
+//! Contesting a dispute from local files.
+//!
+//! [`DisputeEvidenceBuilder`] uploads the files of every evidence category
+//! as [`DocumentPurpose::DisputeEvidence`] documents, saves them as a draft
+//! on the dispute and then submits it:
+//!
+//! ```no_run
+//! # use std::path::Path;
+//! # use rusty_razorpay::{
+//! #     dispute::Dispute,
+//! #     dispute_evidence::{DisputeEvidenceBuilder, EvidenceCategory},
+//! #     document::DocumentFile,
+//! #     Razorpay,
+//! # };
+//! # async fn contest(razorpay: &Razorpay, dispute: &Dispute) {
+//! let builder = DisputeEvidenceBuilder::new(dispute, "Delivered on time")
+//!     .file(
+//!         EvidenceCategory::ShippingProof,
+//!         DocumentFile::Path(Path::new("awb.pdf")),
+//!     )
+//!     .file(
+//!         EvidenceCategory::BillingProof,
+//!         DocumentFile::Path(Path::new("invoice.pdf")),
+//!     );
+//!
+//! if let Some(warning) = builder.deadline_warning() {
+//!     eprintln!("{} must be answered in {}", dispute.id, warning.time_left);
+//! }
+//!
+//! let dispute = builder.contest(razorpay).await;
+//! # }
+//! ```
+//!
+//! If uploading, saving or submitting the evidence fails, the [`DisputeEvidenceError`] keeps
+//! the documents uploaded so far, so they can be passed to
+//! [`DisputeEvidenceBuilder::document`] instead of being uploaded again.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::{Display, Formatter, Result as FormatterResult};
+#[cfg(feature = "std")]
+use std::fmt::{Display, Formatter, Result as FormatterResult};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    dispute::{
+        ContestDispute, ContestDisputeAction, ContestDisputeOtherEvidence,
+        Dispute,
+    },
+    document::{CreateDocument, Document, DocumentFile, DocumentPurpose},
+    error::{RazorpayError, RazorpayResult},
+    DisputeId, DocumentId, Razorpay,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EvidenceCategory {
+    ShippingProof,
+    BillingProof,
+    CancellationProof,
+    CustomerCommunication,
+    ProofOfService,
+    ExplanationLetter,
+    RefundConfirmation,
+    AccessActivityLog,
+    RefundCancellationPolicy,
+    TermAndConditions,
+    /// Evidence of a type of its own, sent in `others`.
+    Other(String),
+}
+
+/// Sent when a dispute has to be answered within
+/// [`DisputeEvidenceBuilder::warn_within`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisputeDeadlineWarning {
+    pub dispute_id: DisputeId,
+    pub respond_by: DateTime<Utc>,
+    /// Negative once `respond_by` has passed.
+    pub time_left: Duration,
+}
+
+impl DisputeDeadlineWarning {
+    pub fn is_overdue(&self) -> bool {
+        self.time_left <= Duration::zero()
+    }
+}
+
+fn deadline_warning(
+    dispute: &Dispute,
+    warn_within: Duration,
+    now: DateTime<Utc>,
+) -> Option<DisputeDeadlineWarning> {
+    let time_left = dispute.respond_by - now;
+
+    (time_left <= warn_within).then(|| DisputeDeadlineWarning {
+        dispute_id: dispute.id.clone(),
+        respond_by: dispute.respond_by,
+        time_left,
+    })
+}
+
+/// Returned when the evidence could not be uploaded, saved or submitted.
+#[derive(Debug)]
+pub struct DisputeEvidenceError {
+    pub error: RazorpayError,
+    /// The documents given beforehand and every one uploaded before the
+    /// error.
+    pub documents: Vec<(EvidenceCategory, DocumentId)>,
+}
+
+impl Display for DisputeEvidenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        write!(
+            f,
+            "{} ({} documents uploaded)",
+            self.error,
+            self.documents.len()
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DisputeEvidenceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<DisputeEvidenceError> for RazorpayError {
+    fn from(error: DisputeEvidenceError) -> Self {
+        error.error
+    }
+}
+
+/// Collects the evidence contesting a dispute, see the
+/// [module docs](self).
+#[derive(Debug)]
+pub struct DisputeEvidenceBuilder<'a> {
+    dispute: &'a Dispute,
+    amount: u64,
+    summary: &'a str,
+    files: Vec<(EvidenceCategory, DocumentFile<'a>)>,
+    documents: Vec<(EvidenceCategory, DocumentId)>,
+    warn_within: Duration,
+}
+
+impl<'a> DisputeEvidenceBuilder<'a> {
+    /// Contests the whole amount of the dispute, warning when less than
+    /// three days are left to respond.
+    pub fn new(dispute: &'a Dispute, summary: &'a str) -> Self {
+        Self {
+            dispute,
+            amount: dispute.amount,
+            summary,
+            files: Vec::new(),
+            documents: Vec::new(),
+            warn_within: Duration::days(3),
+        }
+    }
+
+    /// The amount contested, less than the disputed amount to accept part of
+    /// the dispute.
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// A file uploaded before the evidence is saved.
+    pub fn file(
+        mut self,
+        category: EvidenceCategory,
+        file: DocumentFile<'a>,
+    ) -> Self {
+        self.files.push((category, file));
+        self
+    }
+
+    /// A document uploaded beforehand.
+    pub fn document(
+        mut self,
+        category: EvidenceCategory,
+        document_id: DocumentId,
+    ) -> Self {
+        self.documents.push((category, document_id));
+        self
+    }
+
+    pub fn warn_within(mut self, warn_within: Duration) -> Self {
+        self.warn_within = warn_within;
+        self
+    }
+
+    /// Returns a warning if `respond_by` is within
+    /// [`warn_within`](Self::warn_within) or has passed.
+    pub fn deadline_warning(&self) -> Option<DisputeDeadlineWarning> {
+        deadline_warning(self.dispute, self.warn_within, Utc::now())
+    }
+
+    /// Uploads the files and saves the evidence as a draft, which can still
+    /// be changed from the dashboard before it is submitted.
+    pub async fn save_draft(
+        self,
+        razorpay: &Razorpay,
+    ) -> Result<DisputeEvidenceDraft<'a>, DisputeEvidenceError> {
+        let mut documents = self.documents;
+
+        for (category, file) in self.files {
+            let document = Document::create(
+                razorpay,
+                CreateDocument {
+                    file,
+                    purpose: DocumentPurpose::DisputeEvidence,
+                    mime_type: None,
+                },
+            )
+            .await;
+
+            match document {
+                Ok(document) => documents.push((category, document.id)),
+                Err(error) => {
+                    return Err(DisputeEvidenceError { error, documents })
+                }
+            }
+        }
+
+        let mut draft = DisputeEvidenceDraft {
+            dispute: self.dispute.clone(),
+            amount: self.amount,
+            summary: self.summary,
+            documents,
+            warning: None,
+        };
+
+        draft.dispute =
+            match draft.contest(razorpay, ContestDisputeAction::Draft).await {
+                Ok(dispute) => dispute,
+                Err(error) => {
+                    return Err(DisputeEvidenceError {
+                        error,
+                        documents: draft.documents,
+                    })
+                }
+            };
+        draft.warning =
+            deadline_warning(&draft.dispute, self.warn_within, Utc::now());
+
+        Ok(draft)
+    }
+
+    /// Uploads the files, saves the evidence as a draft and submits it.
+    pub async fn contest(
+        self,
+        razorpay: &Razorpay,
+    ) -> Result<Dispute, DisputeEvidenceError> {
+        let draft = self.save_draft(razorpay).await?;
+        let documents = draft.documents.clone();
+
+        draft
+            .submit(razorpay)
+            .await
+            .map_err(|error| DisputeEvidenceError { error, documents })
+    }
+}
+
+/// Evidence saved on a dispute but not yet submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisputeEvidenceDraft<'a> {
+    /// The dispute as returned after saving the draft.
+    pub dispute: Dispute,
+    pub amount: u64,
+    pub summary: &'a str,
+    /// Every document of the evidence, including the uploaded files.
+    pub documents: Vec<(EvidenceCategory, DocumentId)>,
+    pub warning: Option<DisputeDeadlineWarning>,
+}
+
+impl DisputeEvidenceDraft<'_> {
+    fn document_ids(&self, category: &EvidenceCategory) -> Vec<&str> {
+        self.documents
+            .iter()
+            .filter(|(document_category, _)| document_category == category)
+            .map(|(_, document_id)| document_id.as_str())
+            .collect()
+    }
+
+    async fn contest(
+        &self,
+        razorpay: &Razorpay,
+        action: ContestDisputeAction,
+    ) -> RazorpayResult<Dispute> {
+        let shipping_proof =
+            self.document_ids(&EvidenceCategory::ShippingProof);
+        let billing_proof = self.document_ids(&EvidenceCategory::BillingProof);
+        let cancellation_proof =
+            self.document_ids(&EvidenceCategory::CancellationProof);
+        let customer_communication =
+            self.document_ids(&EvidenceCategory::CustomerCommunication);
+        let proof_of_service =
+            self.document_ids(&EvidenceCategory::ProofOfService);
+        let explanation_letter =
+            self.document_ids(&EvidenceCategory::ExplanationLetter);
+        let refund_confirmation =
+            self.document_ids(&EvidenceCategory::RefundConfirmation);
+        let access_activity_log =
+            self.document_ids(&EvidenceCategory::AccessActivityLog);
+        let refund_cancellation_policy =
+            self.document_ids(&EvidenceCategory::RefundCancellationPolicy);
+        let term_and_conditions =
+            self.document_ids(&EvidenceCategory::TermAndConditions);
+
+        // every other type is sent once with all of its documents
+        let mut other_types: Vec<&str> = Vec::new();
+        for (category, _) in &self.documents {
+            if let EvidenceCategory::Other(type_) = category {
+                if !other_types.contains(&type_.as_str()) {
+                    other_types.push(type_);
+                }
+            }
+        }
+        let other_document_ids: Vec<Vec<&str>> = other_types
+            .iter()
+            .map(|type_| {
+                self.document_ids(&EvidenceCategory::Other((*type_).into()))
+            })
+            .collect();
+        let others: Vec<ContestDisputeOtherEvidence> = other_types
+            .iter()
+            .zip(&other_document_ids)
+            .map(|(type_, document_ids)| ContestDisputeOtherEvidence {
+                type_,
+                document_ids,
+            })
+            .collect();
+
+        fn non_empty<T>(items: &[T]) -> Option<&[T]> {
+            (!items.is_empty()).then_some(items)
+        }
+
+        Dispute::contest(
+            razorpay,
+            &self.dispute.id,
+            ContestDispute {
+                amount: self.amount,
+                summary: self.summary,
+                shipping_proof: non_empty(&shipping_proof),
+                billing_proof: non_empty(&billing_proof),
+                cancellation_proof: non_empty(&cancellation_proof),
+                customer_communication: non_empty(&customer_communication),
+                proof_of_service: non_empty(&proof_of_service),
+                explanation_letter: non_empty(&explanation_letter),
+                refund_confirmation: non_empty(&refund_confirmation),
+                access_activity_log: non_empty(&access_activity_log),
+                refund_cancellation_policy: non_empty(
+                    &refund_cancellation_policy,
+                ),
+                term_and_conditions: non_empty(&term_and_conditions),
+                others: non_empty(&others),
+                action,
+            },
+        )
+        .await
+    }
+
+    /// Submits the evidence, it can't be changed afterwards.
+    pub async fn submit(self, razorpay: &Razorpay) -> RazorpayResult<Dispute> {
+        self.contest(razorpay, ContestDisputeAction::Submit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::error::RazorpayErrorKind;
+
+    fn dispute() -> Dispute {
+        serde_json::from_value(json!({
+            "entity": "dispute", "id": "disp_1", "payment_id": "pay_1",
+            "amount": 10000, "currency": "INR", "amount_deducted": 0,
+            "reason_code": "chargeback", "reason_description": "",
+            "respond_by": 1_700_000_000, "status": "open",
+            "phase": "chargeback", "created_at": 1_600_000_000,
+            "evidence": {
+                "amount": 10000, "summary": "", "shipping_proof": null,
+                "billing_proof": null, "cancellation_proof": null,
+                "customer_communication": null, "proof_of_service": null,
+                "explanation_letter": null, "refund_confirmation": null,
+                "access_activity_log": null,
+                "refund_cancellation_policy": null,
+                "term_and_conditions": null, "others": null,
+                "submitted_at": null
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn keeps_the_documents_of_a_failed_upload() {
+        let razorpay = Razorpay::new("rzp_test_key", "secret");
+        let dispute = dispute();
+
+        let error = DisputeEvidenceBuilder::new(&dispute, "Delivered")
+            .document(
+                EvidenceCategory::BillingProof,
+                "doc_invoice".parse().unwrap(),
+            )
+            .file(
+                EvidenceCategory::ShippingProof,
+                DocumentFile::Path(Path::new("does/not/exist.pdf")),
+            )
+            .contest(&razorpay)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.error.kind(), RazorpayErrorKind::Io);
+        let documents: Vec<(EvidenceCategory, &str)> = error
+            .documents
+            .iter()
+            .map(|(category, document_id)| {
+                (category.clone(), document_id.as_str())
+            })
+            .collect();
+        assert_eq!(
+            documents,
+            [(EvidenceCategory::BillingProof, "doc_invoice")]
+        );
+    }
+}
@@ -0,0 +1,465 @@
+//! Tracking the `respond_by` deadlines of open disputes, a dispute left
+//! unanswered past its deadline is lost.
+//!
+//! ```no_run
+//! # use chrono::Duration;
+//! # use rusty_razorpay::{dispute_monitor::DisputeMonitor, Razorpay};
+//! # async fn monitor(razorpay: &Razorpay) {
+//! let report = DisputeMonitor::new()
+//!     .critical_within(Duration::hours(24))
+//!     .warning_within(Duration::days(5))
+//!     .run(razorpay, None)
+//!     .await
+//!     .unwrap();
+//!
+//! print!("{}", report.to_csv());
+//! # }
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::fmt::Write;
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    common::{Currency, Filter},
+    dispute::{Dispute, DisputePhase, DisputeStatus},
+    error::RazorpayResult,
+    pagination::collect_all,
+    DisputeId, PaymentId, Razorpay,
+};
+
+/// How soon an open dispute has to be answered, from most to least urgent.
+#[derive(
+    Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeUrgency {
+    /// `respond_by` has passed.
+    Overdue,
+    /// Due within [`DisputeMonitor::critical_within`].
+    Critical,
+    /// Due within [`DisputeMonitor::warning_within`].
+    Warning,
+    /// Not due any time soon.
+    Normal,
+}
+
+impl DisputeUrgency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeUrgency::Overdue => "overdue",
+            DisputeUrgency::Critical => "critical",
+            DisputeUrgency::Warning => "warning",
+            DisputeUrgency::Normal => "normal",
+        }
+    }
+}
+
+/// An open dispute along with the time left to answer it.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct DisputeDeadline {
+    pub dispute_id: DisputeId,
+    pub payment_id: PaymentId,
+    pub phase: DisputePhase,
+    pub amount: u64,
+    pub currency: Currency,
+    pub reason_code: String,
+    pub respond_by: DateTime<Utc>,
+    /// Whole hours until `respond_by` rounded down, negative once it has
+    /// passed.
+    pub hours_left: i64,
+    pub urgency: DisputeUrgency,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct DisputeUrgencyCounts {
+    pub overdue: usize,
+    pub critical: usize,
+    pub warning: usize,
+    pub normal: usize,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct DisputePhaseCounts {
+    pub fraud: usize,
+    pub retrieval: usize,
+    pub chargeback: usize,
+    pub pre_arbitration: usize,
+    pub arbitration: usize,
+}
+
+/// The open disputes that need action, most urgent first.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct DisputeDeadlineReport {
+    pub generated_at: DateTime<Utc>,
+    /// Every open dispute, including those not needing action yet.
+    pub open: usize,
+    pub by_urgency: DisputeUrgencyCounts,
+    /// Open disputes needing action per phase.
+    pub by_phase: DisputePhaseCounts,
+    pub disputes: Vec<DisputeDeadline>,
+}
+
+fn phase_str(phase: &DisputePhase) -> &'static str {
+    match phase {
+        DisputePhase::Fraud => "fraud",
+        DisputePhase::Retrieval => "retrieval",
+        DisputePhase::Chargeback => "chargeback",
+        DisputePhase::PreArbitration => "pre_arbitration",
+        DisputePhase::Arbitration => "arbitration",
+    }
+}
+
+// the ISO code of a currency, as sent by the API
+fn currency_code(currency: &Currency) -> String {
+    match serde_json::to_value(currency) {
+        Ok(Value::String(code)) => code,
+        _ => String::new(),
+    }
+}
+
+// quotes a CSV field if it contains a separator, a quote or a line break
+fn csv_field(out: &mut String, value: &str) {
+    if value.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&value.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(value);
+    }
+}
+
+impl DisputeDeadlineReport {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// One line per dispute needing action, with a header line.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "dispute_id,payment_id,phase,amount,currency,reason_code,\
+             respond_by,hours_left,urgency\n",
+        );
+
+        for deadline in &self.disputes {
+            let _ = write!(
+                out,
+                "{},{},{},{},{},",
+                deadline.dispute_id,
+                deadline.payment_id,
+                phase_str(&deadline.phase),
+                deadline.amount,
+                currency_code(&deadline.currency),
+            );
+            csv_field(&mut out, &deadline.reason_code);
+            let _ = writeln!(
+                out,
+                ",{},{},{}",
+                deadline
+                    .respond_by
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+                deadline.hours_left,
+                deadline.urgency.as_str(),
+            );
+        }
+
+        out
+    }
+}
+
+/// Classifies open disputes by the time left until their `respond_by`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisputeMonitor {
+    critical_within: Duration,
+    warning_within: Duration,
+}
+
+impl Default for DisputeMonitor {
+    fn default() -> Self {
+        Self {
+            critical_within: Duration::days(1),
+            warning_within: Duration::days(3),
+        }
+    }
+}
+
+impl DisputeMonitor {
+    /// Disputes due within a day are critical, within three days a warning.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn critical_within(mut self, critical_within: Duration) -> Self {
+        self.critical_within = critical_within;
+        self
+    }
+
+    pub fn warning_within(mut self, warning_within: Duration) -> Self {
+        self.warning_within = warning_within;
+        self
+    }
+
+    pub fn urgency(
+        &self,
+        dispute: &Dispute,
+        now: DateTime<Utc>,
+    ) -> DisputeUrgency {
+        let time_left = dispute.respond_by - now;
+
+        if time_left <= Duration::zero() {
+            DisputeUrgency::Overdue
+        } else if time_left <= self.critical_within {
+            DisputeUrgency::Critical
+        } else if time_left <= self.warning_within {
+            DisputeUrgency::Warning
+        } else {
+            DisputeUrgency::Normal
+        }
+    }
+
+    /// Builds a report from disputes fetched beforehand, disputes that
+    /// aren't open are skipped.
+    pub fn report<I>(
+        &self,
+        disputes: I,
+        now: DateTime<Utc>,
+    ) -> DisputeDeadlineReport
+    where
+        I: IntoIterator<Item = Dispute>,
+    {
+        let mut report = DisputeDeadlineReport {
+            generated_at: now,
+            open: 0,
+            by_urgency: DisputeUrgencyCounts::default(),
+            by_phase: DisputePhaseCounts::default(),
+            disputes: Vec::new(),
+        };
+
+        for dispute in disputes {
+            if dispute.status != DisputeStatus::Open {
+                continue;
+            }
+            report.open += 1;
+
+            let urgency = self.urgency(&dispute, now);
+            match urgency {
+                DisputeUrgency::Overdue => report.by_urgency.overdue += 1,
+                DisputeUrgency::Critical => report.by_urgency.critical += 1,
+                DisputeUrgency::Warning => report.by_urgency.warning += 1,
+                DisputeUrgency::Normal => {
+                    report.by_urgency.normal += 1;
+                    continue;
+                }
+            }
+
+            match dispute.phase {
+                DisputePhase::Fraud => report.by_phase.fraud += 1,
+                DisputePhase::Retrieval => report.by_phase.retrieval += 1,
+                DisputePhase::Chargeback => report.by_phase.chargeback += 1,
+                DisputePhase::PreArbitration => {
+                    report.by_phase.pre_arbitration += 1
+                }
+                DisputePhase::Arbitration => report.by_phase.arbitration += 1,
+            }
+
+            report.disputes.push(DisputeDeadline {
+                hours_left: (dispute.respond_by - now)
+                    .num_seconds()
+                    .div_euclid(3600),
+                dispute_id: dispute.id,
+                payment_id: dispute.payment_id,
+                phase: dispute.phase,
+                amount: dispute.amount,
+                currency: dispute.currency,
+                reason_code: dispute.reason_code,
+                respond_by: dispute.respond_by,
+                urgency,
+            });
+        }
+
+        report.disputes.sort_by(|a, b| {
+            a.urgency
+                .cmp(&b.urgency)
+                .then_with(|| a.respond_by.cmp(&b.respond_by))
+        });
+
+        report
+    }
+
+    /// Lists every dispute created within `filter` and reports the open
+    /// ones needing action.
+    pub async fn run(
+        &self,
+        razorpay: &Razorpay,
+        filter: Option<Filter>,
+    ) -> RazorpayResult<DisputeDeadlineReport> {
+        let disputes =
+            collect_all(Dispute::list_stream(razorpay, filter), None).await?;
+
+        Ok(self.report(disputes, Utc::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+
+    fn dispute(
+        id: &str,
+        status: &str,
+        phase: &str,
+        respond_by: DateTime<Utc>,
+    ) -> Dispute {
+        serde_json::from_value(json!({
+            "entity": "dispute", "id": id, "payment_id": "pay_1",
+            "amount": 10000, "currency": "INR", "amount_deducted": 0,
+            "reason_code": "chargeback", "reason_description": "",
+            "respond_by": respond_by.timestamp(), "status": status,
+            "phase": phase, "created_at": 1_600_000_000,
+            "evidence": {
+                "amount": 10000, "summary": "", "shipping_proof": null,
+                "billing_proof": null, "cancellation_proof": null,
+                "customer_communication": null, "proof_of_service": null,
+                "explanation_letter": null, "refund_confirmation": null,
+                "access_activity_log": null,
+                "refund_cancellation_policy": null,
+                "term_and_conditions": null, "others": null,
+                "submitted_at": null
+            }
+        }))
+        .unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn buckets_open_disputes_by_deadline() {
+        let now = now();
+        let disputes = vec![
+            dispute(
+                "disp_normal",
+                "open",
+                "chargeback",
+                now + Duration::days(5),
+            ),
+            dispute("disp_warning", "open", "fraud", now + Duration::days(2)),
+            dispute(
+                "disp_critical",
+                "open",
+                "chargeback",
+                now + Duration::hours(23) + Duration::minutes(30),
+            ),
+            dispute("disp_due_now", "open", "retrieval", now),
+            dispute(
+                "disp_overdue",
+                "open",
+                "arbitration",
+                now - Duration::minutes(30),
+            ),
+            dispute("disp_won", "won", "chargeback", now - Duration::days(1)),
+        ];
+
+        let report = DisputeMonitor::new().report(disputes, now);
+
+        assert_eq!(report.open, 5);
+        assert_eq!(
+            report.by_urgency,
+            DisputeUrgencyCounts {
+                overdue: 2,
+                critical: 1,
+                warning: 1,
+                normal: 1,
+            }
+        );
+        assert_eq!(
+            report.by_phase,
+            DisputePhaseCounts {
+                fraud: 1,
+                retrieval: 1,
+                chargeback: 1,
+                pre_arbitration: 0,
+                arbitration: 1,
+            }
+        );
+
+        // most urgent first, then the earliest deadline
+        let listed: Vec<(&str, DisputeUrgency, i64)> = report
+            .disputes
+            .iter()
+            .map(|d| (d.dispute_id.as_str(), d.urgency, d.hours_left))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                ("disp_overdue", DisputeUrgency::Overdue, -1),
+                ("disp_due_now", DisputeUrgency::Overdue, 0),
+                ("disp_critical", DisputeUrgency::Critical, 23),
+                ("disp_warning", DisputeUrgency::Warning, 48),
+            ]
+        );
+    }
+
+    #[test]
+    fn thresholds_are_configurable() {
+        let now = now();
+        let monitor = DisputeMonitor::new()
+            .critical_within(Duration::hours(6))
+            .warning_within(Duration::days(7));
+
+        let due = |hours| {
+            monitor.urgency(
+                &dispute(
+                    "disp_1",
+                    "open",
+                    "chargeback",
+                    now + Duration::hours(hours),
+                ),
+                now,
+            )
+        };
+
+        assert_eq!(due(6), DisputeUrgency::Critical);
+        assert_eq!(due(7), DisputeUrgency::Warning);
+        assert_eq!(due(24 * 7), DisputeUrgency::Warning);
+        assert_eq!(due(24 * 7 + 1), DisputeUrgency::Normal);
+    }
+
+    #[test]
+    fn writes_csv_with_iso_currency_and_escaped_fields() {
+        let now = now();
+        let mut quoted = dispute(
+            "disp_1",
+            "open",
+            "pre_arbitration",
+            now + Duration::hours(5),
+        );
+        quoted.reason_code = "goods \"not\" received, partially".to_owned();
+        let plain =
+            dispute("disp_2", "open", "fraud", now - Duration::hours(2));
+
+        let csv = DisputeMonitor::new().report([quoted, plain], now).to_csv();
+
+        assert_eq!(
+            csv,
+            "dispute_id,payment_id,phase,amount,currency,reason_code,\
+             respond_by,hours_left,urgency\n\
+             disp_2,pay_1,fraud,10000,INR,chargeback,\
+             2024-03-01T10:00:00Z,-2,overdue\n\
+             disp_1,pay_1,pre_arbitration,10000,INR,\
+             \"goods \"\"not\"\" received, partially\",\
+             2024-03-01T17:00:00Z,5,critical\n"
+        );
+    }
+}
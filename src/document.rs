@@ -1,8 +1,24 @@
 #[cfg(not(feature = "std"))]
-use alloc::{format, string::String};
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::{Debug, Formatter, Result as FormatterResult};
+#[cfg(feature = "std")]
+use std::{
+    fmt::{Debug, Formatter, Result as FormatterResult},
+    future::ready,
+    io,
+    path::Path,
+};
 
 use chrono::{serde::ts_seconds, DateTime, Utc};
-use serde::Deserialize;
+#[cfg(feature = "std")]
+use futures_util::{stream, StreamExt};
+use reqwest::multipart::Part;
+#[cfg(feature = "std")]
+use reqwest::Body;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use crate::{
     api::RequestParams,
@@ -11,13 +27,13 @@ use crate::{
     Razorpay,
 };
 
-#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum DocumentPurpose {
     DisputeEvidence,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum DocumentMimeType {
     #[serde(rename = "image/jpg")]
     ImageJpg,
@@ -29,6 +45,39 @@ pub enum DocumentMimeType {
     ApplicationPdf,
 }
 
+impl DocumentMimeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentMimeType::ImageJpg => "image/jpg",
+            DocumentMimeType::ImageJpeg => "image/jpeg",
+            DocumentMimeType::ImagePng => "image/png",
+            DocumentMimeType::ApplicationPdf => "application/pdf",
+        }
+    }
+
+    /// Detects the type of a file from its first bytes, falling back to the
+    /// extension of its name.
+    pub fn detect(name: &str, content: &[u8]) -> Option<DocumentMimeType> {
+        if content.starts_with(b"%PDF-") {
+            return Some(DocumentMimeType::ApplicationPdf);
+        }
+        if content.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return Some(DocumentMimeType::ImagePng);
+        }
+        if content.starts_with(&[0xff, 0xd8, 0xff]) {
+            return Some(DocumentMimeType::ImageJpeg);
+        }
+
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "pdf" => Some(DocumentMimeType::ApplicationPdf),
+            "png" => Some(DocumentMimeType::ImagePng),
+            "jpg" | "jpeg" => Some(DocumentMimeType::ImageJpeg),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
 #[serde(tag = "entity", rename = "document")]
 pub struct Document {
@@ -41,13 +90,156 @@ pub struct Document {
     pub created_at: DateTime<Utc>,
 }
 
+/// The file uploaded by [`Document::create`], files and readers are
+/// streamed as they are sent.
+pub enum DocumentFile<'a> {
+    #[cfg(feature = "std")]
+    Path(&'a Path),
+    Bytes {
+        name: &'a str,
+        content: &'a [u8],
+    },
+    #[cfg(feature = "std")]
+    Reader {
+        name: &'a str,
+        reader: Box<dyn AsyncRead + Unpin + Send>,
+    },
+}
+
+/// Enough of the start of a file for [`DocumentMimeType::detect`].
+const HEAD_LEN: usize = 8;
+
+#[cfg(feature = "std")]
+const CHUNK_LEN: usize = 64 * 1024;
+
+/// Reads the first bytes of `reader` and streams them along with the rest of
+/// it.
+#[cfg(feature = "std")]
+async fn stream_reader(
+    mut reader: Box<dyn AsyncRead + Unpin + Send>,
+) -> io::Result<(Vec<u8>, Body)> {
+    let mut head = vec![0; HEAD_LEN];
+    let mut len = 0;
+    while len < head.len() {
+        match reader.read(&mut head[len..]).await? {
+            0 => break,
+            read => len += read,
+        }
+    }
+    head.truncate(len);
+
+    let rest = stream::unfold(reader, |mut reader| async move {
+        let mut chunk = vec![0; CHUNK_LEN];
+        match reader.read(&mut chunk).await {
+            Ok(0) => None,
+            Ok(read) => {
+                chunk.truncate(read);
+                Some((Ok(chunk), reader))
+            }
+            Err(error) => Some((Err(error), reader)),
+        }
+    });
+    let body =
+        stream::once(ready(Ok::<_, io::Error>(head.clone()))).chain(rest);
+
+    Ok((head, Body::wrap_stream(body)))
+}
+
+impl Debug for DocumentFile<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        match self {
+            #[cfg(feature = "std")]
+            DocumentFile::Path(path) => {
+                f.debug_tuple("Path").field(path).finish()
+            }
+            DocumentFile::Bytes { name, content } => f
+                .debug_struct("Bytes")
+                .field("name", name)
+                .field("len", &content.len())
+                .finish(),
+            #[cfg(feature = "std")]
+            DocumentFile::Reader { name, .. } => f
+                .debug_struct("Reader")
+                .field("name", name)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateDocument<'a> {
+    pub file: DocumentFile<'a>,
+    pub purpose: DocumentPurpose,
+    /// Detected from the file when unset, files of an unknown type are
+    /// left for Razorpay to reject.
+    pub mime_type: Option<DocumentMimeType>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateDocumentFields {
+    purpose: DocumentPurpose,
+}
+
 impl Document {
-    // TODO: add api for creating document
-    //
-    // This time it's not the [docs]' fault it's just that I hate the multipart
-    // thing so left it for the end
-    //
-    // [docs]: https://razorpay.com/docs/api/documents/create
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateDocument<'_>,
+    ) -> RazorpayResult<Document> {
+        // the length of a file is known, letting the request carry a
+        // `Content-Length` instead of being chunked
+        let (name, head, part) = match params.file {
+            #[cfg(feature = "std")]
+            DocumentFile::Path(path) => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let file = tokio::fs::File::open(path).await?;
+                let len = file.metadata().await?.len();
+                let (head, body) = stream_reader(Box::new(file)).await?;
+                (name, head, Part::stream_with_length(body, len))
+            }
+            DocumentFile::Bytes { name, content } => {
+                let head = content[..content.len().min(HEAD_LEN)].to_vec();
+                (name.to_owned(), head, Part::bytes(content.to_vec()))
+            }
+            #[cfg(feature = "std")]
+            DocumentFile::Reader { name, reader } => {
+                let (head, body) = stream_reader(reader).await?;
+                (name.to_owned(), head, Part::stream(body))
+            }
+        };
+
+        let mime_type = params
+            .mime_type
+            .or_else(|| DocumentMimeType::detect(&name, &head));
+        let file = part.file_name(name).mime_str(
+            mime_type
+                .as_ref()
+                .map_or("application/octet-stream", DocumentMimeType::as_str),
+        )?;
+
+        let res = razorpay
+            .api
+            .post_multipart(
+                RequestParams {
+                    url: "/documents".to_owned(),
+                    version: None,
+                    idempotency_key: None,
+                    data: Some(CreateDocumentFields {
+                        purpose: params.purpose,
+                    }),
+                },
+                "file",
+                file,
+            )
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(document) => Ok(document),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
 
     pub async fn fetch(
         razorpay: &Razorpay,
@@ -58,6 +250,7 @@ impl Document {
             .get(RequestParams {
                 url: format!("/documents/{}", document_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -68,11 +261,100 @@ impl Document {
         }
     }
 
-    // TODO: add api for fetching contents of a document
-    //
-    // It will take more time as the contents of the document would be
-    // some kinda binary and we may have to update the current api to
-    // add a method to allow us to get the raw body
-    //
-    // [docs]: https://razorpay.com/docs/api/documents/fetch-content
+    /// Downloads the file of a document.
+    pub async fn fetch_content(
+        razorpay: &Razorpay,
+        document_id: &DocumentId,
+    ) -> RazorpayResult<Vec<u8>> {
+        let res = razorpay
+            .api
+            .get_raw(RequestParams {
+                url: format!("/documents/{}/content", document_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        Ok(res.bytes().await?.to_vec())
+    }
+
+    /// Downloads the file of a document into `writer` without holding it in
+    /// memory, returns the number of bytes written.
+    #[cfg(feature = "std")]
+    pub async fn fetch_content_to<W>(
+        razorpay: &Razorpay,
+        document_id: &DocumentId,
+        writer: &mut W,
+    ) -> RazorpayResult<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let mut res = razorpay
+            .api
+            .get_raw(RequestParams {
+                url: format!("/documents/{}/content", document_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        let mut written = 0;
+        while let Some(chunk) = res.chunk().await? {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_mime_type_from_magic_bytes() {
+        let detect =
+            |content: &[u8]| DocumentMimeType::detect("upload", content);
+
+        assert_eq!(
+            detect(b"%PDF-1.7\n"),
+            Some(DocumentMimeType::ApplicationPdf)
+        );
+        assert_eq!(
+            detect(b"\x89PNG\r\n\x1a\n\0\0"),
+            Some(DocumentMimeType::ImagePng)
+        );
+        assert_eq!(
+            detect(&[0xff, 0xd8, 0xff, 0xe0]),
+            Some(DocumentMimeType::ImageJpeg)
+        );
+        // the content wins over a misleading name
+        assert_eq!(
+            DocumentMimeType::detect("scan.jpg", b"%PDF-1.4"),
+            Some(DocumentMimeType::ApplicationPdf)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_extension() {
+        let detect = |name: &str| DocumentMimeType::detect(name, b"plain text");
+
+        assert_eq!(detect("awb.pdf"), Some(DocumentMimeType::ApplicationPdf));
+        assert_eq!(detect("photo.PNG"), Some(DocumentMimeType::ImagePng));
+        assert_eq!(detect("a.b.jpg"), Some(DocumentMimeType::ImageJpeg));
+        assert_eq!(detect("photo.jpeg"), Some(DocumentMimeType::ImageJpeg));
+    }
+
+    #[test]
+    fn detects_nothing_without_magic_bytes_or_known_extension() {
+        assert_eq!(DocumentMimeType::detect("notes.txt", b"plain text"), None);
+        assert_eq!(DocumentMimeType::detect("README", b"plain text"), None);
+        assert_eq!(DocumentMimeType::detect("", b""), None);
+        // too short to hold any of the signatures
+        assert_eq!(DocumentMimeType::detect("upload", &[0xff, 0xd8]), None);
+    }
 }
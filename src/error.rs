@@ -2,9 +2,15 @@
 use alloc::string::String;
 #[cfg(not(feature = "std"))]
 use core::fmt::{Display, Formatter, Result as FormatterResult};
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
 #[cfg(feature = "std")]
-use std::fmt::{Display, Formatter, Result as FormatterResult};
+use std::{
+    fmt::{Display, Formatter, Result as FormatterResult},
+    time::Duration,
+};
 
+use reqwest::StatusCode;
 use serde::Deserialize;
 
 use crate::{
@@ -12,6 +18,57 @@ use crate::{
     util::{debug_option, display_option},
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RazorpayErrorKind {
+    /// The request was rejected as invalid (`BAD_REQUEST_ERROR`).
+    BadRequest,
+    /// The API keys are missing, invalid or not allowed to do this.
+    Authentication,
+    /// The requested entity or endpoint does not exist.
+    NotFound,
+    /// Too many requests were sent, see [`RazorpayError::retry_after`].
+    RateLimited,
+    /// The payment gateway or bank failed (`GATEWAY_ERROR`).
+    Gateway,
+    /// Razorpay failed to process the request (`SERVER_ERROR`).
+    Server,
+    /// The request could not be sent or the response could not be read.
+    Network,
+    /// A request or response body could not be (de)serialized.
+    Serialization,
+    /// A local file or stream could not be read or written.
+    Io,
+    /// None of the above.
+    Unknown,
+}
+
+impl RazorpayErrorKind {
+    fn from_status(status: StatusCode) -> Option<Self> {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Some(Self::Authentication)
+            }
+            StatusCode::NOT_FOUND => Some(Self::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Some(Self::RateLimited),
+            StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT => {
+                Some(Self::Gateway)
+            }
+            status if status.is_server_error() => Some(Self::Server),
+            status if status.is_client_error() => Some(Self::BadRequest),
+            _ => None,
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "BAD_REQUEST_ERROR" => Some(Self::BadRequest),
+            "GATEWAY_ERROR" => Some(Self::Gateway),
+            "SERVER_ERROR" => Some(Self::Server),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiError {
     pub code: String,
@@ -21,16 +78,36 @@ pub struct ApiError {
     pub reason: Option<String>,
     pub metadata: Option<Object>,
     pub field: Option<String>,
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+    #[serde(skip)]
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    pub fn kind(&self) -> RazorpayErrorKind {
+        // the status is more specific than the code, e.g. a 401 comes back
+        // as a `BAD_REQUEST_ERROR`
+        self.status
+            .and_then(|status| match RazorpayErrorKind::from_status(status) {
+                Some(RazorpayErrorKind::BadRequest) => None,
+                kind => kind,
+            })
+            .or_else(|| RazorpayErrorKind::from_code(&self.code))
+            .or_else(|| self.status.and_then(RazorpayErrorKind::from_status))
+            .unwrap_or(RazorpayErrorKind::Unknown)
+    }
 }
 
 impl Display for ApiError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
         write!(
             f,
-            "Razorpay Error: {}: {}\n\nsource: {}\nstep: {}\nreason: \
-             {}\nfield: {}\nmetadata: {}",
+            "Razorpay Error: {}: {}\n\nstatus: {}\nsource: {}\nstep: \
+             {}\nreason: {}\nfield: {}\nmetadata: {}",
             self.code,
             self.description,
+            display_option(self.status.as_ref()),
             display_option(self.source.as_ref()),
             display_option(self.step.as_ref()),
             display_option(self.reason.as_ref()),
@@ -46,24 +123,120 @@ impl std::error::Error for ApiError {}
 #[derive(Debug)]
 pub enum RazorpayError {
     ApiError(ApiError),
+    HttpError {
+        status: StatusCode,
+        body: String,
+        retry_after: Option<Duration>,
+    },
     ReqwestError(reqwest::Error),
     SerializationError(serde_json::Error),
+    #[cfg(feature = "std")]
+    IoError(std::io::Error),
 }
 
 impl Display for RazorpayError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
         match self {
             RazorpayError::ApiError(error) => write!(f, "API Error: {}", error),
+            RazorpayError::HttpError { status, body, .. } => {
+                write!(f, "HTTP Error: {}: {}", status, body)
+            }
             RazorpayError::ReqwestError(error) => {
                 write!(f, "Reqwest Error: {}", error)
             }
             RazorpayError::SerializationError(error) => {
                 write!(f, "Serialization Error: {}", error)
             }
+            #[cfg(feature = "std")]
+            RazorpayError::IoError(error) => write!(f, "IO Error: {}", error),
         }
     }
 }
 
+impl RazorpayError {
+    pub fn kind(&self) -> RazorpayErrorKind {
+        match self {
+            RazorpayError::ApiError(error) => error.kind(),
+            RazorpayError::HttpError { status, .. } => {
+                RazorpayErrorKind::from_status(*status)
+                    .unwrap_or(RazorpayErrorKind::Unknown)
+            }
+            RazorpayError::ReqwestError(error) => {
+                if error.is_decode() {
+                    RazorpayErrorKind::Serialization
+                } else if let Some(status) = error.status() {
+                    RazorpayErrorKind::from_status(status)
+                        .unwrap_or(RazorpayErrorKind::Unknown)
+                } else if error.is_timeout()
+                    || error.is_connect()
+                    || error.is_request()
+                    || error.is_body()
+                {
+                    RazorpayErrorKind::Network
+                } else {
+                    RazorpayErrorKind::Unknown
+                }
+            }
+            RazorpayError::SerializationError(_) => {
+                RazorpayErrorKind::Serialization
+            }
+            #[cfg(feature = "std")]
+            RazorpayError::IoError(_) => RazorpayErrorKind::Io,
+        }
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            RazorpayError::ApiError(error) => error.status,
+            RazorpayError::HttpError { status, .. } => Some(*status),
+            RazorpayError::ReqwestError(error) => error.status(),
+            RazorpayError::SerializationError(_) => None,
+            #[cfg(feature = "std")]
+            RazorpayError::IoError(_) => None,
+        }
+    }
+
+    /// The delay requested by the `Retry-After` response header, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            RazorpayError::ApiError(error) => error.retry_after,
+            RazorpayError::HttpError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self.kind() {
+            RazorpayErrorKind::RateLimited
+            | RazorpayErrorKind::Server
+            | RazorpayErrorKind::Network => true,
+            // a `GATEWAY_ERROR` with a 4xx is the bank declining the
+            // payment, which fails the same way when sent again
+            RazorpayErrorKind::Gateway => self
+                .status()
+                .map_or(true, |status| status.is_server_error()),
+            _ => false,
+        }
+    }
+
+    pub fn is_auth_error(&self) -> bool {
+        self.kind() == RazorpayErrorKind::Authentication
+    }
+
+    pub fn is_validation_error(&self) -> bool {
+        self.kind() == RazorpayErrorKind::BadRequest
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == RazorpayErrorKind::NotFound
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.kind() == RazorpayErrorKind::RateLimited
+    }
+}
+
 impl From<reqwest::Error> for RazorpayError {
     fn from(error: reqwest::Error) -> Self {
         RazorpayError::ReqwestError(error)
@@ -82,14 +255,110 @@ impl From<serde_json::Error> for RazorpayError {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for RazorpayError {
+    fn from(error: std::io::Error) -> Self {
+        RazorpayError::IoError(error)
+    }
+}
+
 #[cfg(feature = "std")]
 impl std::error::Error for RazorpayError {}
 
 pub type RazorpayResult<T> = Result<T, RazorpayError>;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 pub(crate) enum InternalApiResult<T> {
     Ok(T),
     Err { error: ApiError },
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn api_error(status: u16, code: &str) -> RazorpayError {
+        let mut error: ApiError = serde_json::from_value(json!({
+            "code": code,
+            "description": "failed",
+        }))
+        .unwrap();
+        error.status = Some(StatusCode::from_u16(status).unwrap());
+        RazorpayError::ApiError(error)
+    }
+
+    fn http_error(status: u16) -> RazorpayError {
+        RazorpayError::HttpError {
+            status: StatusCode::from_u16(status).unwrap(),
+            body: "<html></html>".to_owned(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn classifies_statuses() {
+        use RazorpayErrorKind::*;
+
+        for (status, kind) in [
+            (400, BadRequest),
+            (401, Authentication),
+            (403, Authentication),
+            (404, NotFound),
+            (409, BadRequest),
+            (429, RateLimited),
+            (500, Server),
+            (502, Gateway),
+            (503, Server),
+            (504, Gateway),
+            (302, Unknown),
+        ] {
+            assert_eq!(http_error(status).kind(), kind, "{}", status);
+        }
+    }
+
+    #[test]
+    fn classifies_api_errors_by_status_then_code() {
+        use RazorpayErrorKind::*;
+
+        for (status, code, kind) in [
+            (400, "BAD_REQUEST_ERROR", BadRequest),
+            (400, "GATEWAY_ERROR", Gateway),
+            (400, "SERVER_ERROR", Server),
+            (422, "UNKNOWN_ERROR", BadRequest),
+            (401, "BAD_REQUEST_ERROR", Authentication),
+            (404, "BAD_REQUEST_ERROR", NotFound),
+            (429, "BAD_REQUEST_ERROR", RateLimited),
+            (500, "GATEWAY_ERROR", Server),
+            (502, "SERVER_ERROR", Gateway),
+        ] {
+            assert_eq!(
+                api_error(status, code).kind(),
+                kind,
+                "{} {}",
+                status,
+                code
+            );
+        }
+
+        let error: ApiError = serde_json::from_value(json!({
+            "code": "SERVER_ERROR",
+            "description": "failed",
+        }))
+        .unwrap();
+        assert_eq!(error.kind(), Server);
+    }
+
+    #[test]
+    fn retries_gateway_errors_only_on_server_errors() {
+        assert!(!api_error(400, "GATEWAY_ERROR").is_retryable());
+        assert!(api_error(502, "GATEWAY_ERROR").is_retryable());
+        assert!(api_error(504, "BAD_REQUEST_ERROR").is_retryable());
+        assert!(api_error(500, "SERVER_ERROR").is_retryable());
+        assert!(api_error(429, "BAD_REQUEST_ERROR").is_retryable());
+        assert!(!api_error(400, "BAD_REQUEST_ERROR").is_retryable());
+    }
+}
@@ -0,0 +1,270 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+#[cfg(not(feature = "std"))]
+use core::fmt::{Debug, Formatter, Result as FormatterResult};
+#[cfg(feature = "std")]
+use std::fmt::{Debug, Formatter, Result as FormatterResult};
+
+use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    api::RequestParams,
+    card::{CardNetwork, CardTypeExtended},
+    common::{Collection, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::FundAccountId,
+    pagination::paginate,
+    util::{deserialize_notes, mask_card_number},
+    BatchId, ContactId, CustomerId, Razorpay,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FundAccountType {
+    BankAccount,
+    Vpa,
+    Card,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct FundAccountBankAccount {
+    pub name: String,
+    pub ifsc: String,
+    pub bank_name: Option<String>,
+    pub account_number: String,
+    #[serde(default, deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct FundAccountVpa {
+    pub username: Option<String>,
+    pub handle: Option<String>,
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct FundAccountCard {
+    pub name: Option<String>,
+    pub last4: String,
+    pub network: CardNetwork,
+    #[serde(rename = "type")]
+    pub type_: CardTypeExtended,
+    pub issuer: Option<String>,
+}
+
+/// The account a fund account pays out to, keyed by its `account_type`.
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(tag = "account_type", rename_all = "snake_case")]
+pub enum FundAccountDetails {
+    BankAccount {
+        bank_account: FundAccountBankAccount,
+    },
+    Vpa {
+        vpa: FundAccountVpa,
+    },
+    Card {
+        card: FundAccountCard,
+    },
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(tag = "entity", rename = "fund_account")]
+pub struct FundAccount {
+    pub id: FundAccountId,
+    /// Set for RazorpayX fund accounts, used for payouts.
+    pub contact_id: Option<ContactId>,
+    /// Set for fund accounts of customers, used for refunds.
+    pub customer_id: Option<CustomerId>,
+    #[serde(flatten)]
+    pub details: FundAccountDetails,
+    pub active: bool,
+    pub batch_id: Option<BatchId>,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreateFundAccountBankAccount<'a> {
+    pub name: &'a str,
+    pub ifsc: &'a str,
+    pub account_number: &'a str,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreateFundAccountVpa<'a> {
+    pub address: &'a str,
+}
+
+#[derive(Serialize, Clone, Eq, PartialEq)]
+pub struct CreateFundAccountCard<'a> {
+    pub name: &'a str,
+    pub number: &'a str,
+}
+
+impl Debug for CreateFundAccountCard<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        f.debug_struct("CreateFundAccountCard")
+            .field("name", &self.name)
+            .field("number", &mask_card_number(self.number))
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[serde(tag = "account_type", rename_all = "snake_case")]
+pub enum CreateFundAccountDetails<'a> {
+    BankAccount {
+        bank_account: CreateFundAccountBankAccount<'a>,
+    },
+    Vpa {
+        vpa: CreateFundAccountVpa<'a>,
+    },
+    Card {
+        card: CreateFundAccountCard<'a>,
+    },
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreateFundAccount<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<&'a ContactId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    #[serde(flatten)]
+    pub details: CreateFundAccountDetails<'a>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct ListFundAccounts<'a> {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<&'a ContactId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<FundAccountType>,
+}
+
+impl FundAccount {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateFundAccount<'_>,
+    ) -> RazorpayResult<FundAccount> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/fund_accounts".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(fund_account) => Ok(fund_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        fund_account_id: &FundAccountId,
+    ) -> RazorpayResult<FundAccount> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/fund_accounts/{}", fund_account_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(fund_account) => Ok(fund_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: Option<ListFundAccounts<'_>>,
+    ) -> RazorpayResult<Collection<FundAccount>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/fund_accounts".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(fund_accounts) => Ok(fund_accounts),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListFundAccounts<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<FundAccount>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            FundAccount::list(
+                razorpay,
+                Some(ListFundAccounts {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
+    /// Activates or deactivates a fund account, payouts can't be made to
+    /// deactivated fund accounts.
+    pub async fn set_active(
+        razorpay: &Razorpay,
+        fund_account_id: &FundAccountId,
+        active: bool,
+    ) -> RazorpayResult<FundAccount> {
+        let res = razorpay
+            .api
+            .patch(RequestParams {
+                url: format!("/fund_accounts/{}", fund_account_id),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "active": active })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(fund_account) => Ok(fund_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_hides_card_number() {
+        let card = CreateFundAccountCard {
+            name: "Gaurav Kumar",
+            number: "4111111111111234",
+        };
+
+        assert_eq!(
+            format!("{:?}", card),
+            r#"CreateFundAccountCard { name: "Gaurav Kumar", number: "****1234" }"#
+        );
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+
+use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Currency, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    fund_account::FundAccount,
+    ids::FundAccountValidationId,
+    pagination::paginate,
+    util::deserialize_notes,
+    FundAccountId, Razorpay,
+};
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FundAccountValidationStatus {
+    Created,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FundAccountValidationAccountStatus {
+    Active,
+    Invalid,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct FundAccountValidationResults {
+    pub account_status: Option<FundAccountValidationAccountStatus>,
+    /// The name of the account holder as registered with the bank.
+    pub registered_name: Option<String>,
+}
+
+/// A penny drop, validates a fund account by depositing a small amount in
+/// it.
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(tag = "entity", rename = "fund_account.validation")]
+pub struct FundAccountValidation {
+    pub id: FundAccountValidationId,
+    pub fund_account: FundAccount,
+    pub status: FundAccountValidationStatus,
+    pub amount: Option<u64>,
+    pub currency: Option<Currency>,
+    pub results: FundAccountValidationResults,
+    pub utr: Option<String>,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreateFundAccountValidationFundAccount<'a> {
+    pub id: &'a FundAccountId,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreateFundAccountValidation<'a> {
+    /// The RazorpayX account number the amount is deposited from.
+    pub account_number: &'a str,
+    pub fund_account: CreateFundAccountValidationFundAccount<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct ListFundAccountValidations<'a> {
+    pub account_number: &'a str,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+}
+
+impl FundAccountValidation {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateFundAccountValidation<'_>,
+    ) -> RazorpayResult<FundAccountValidation> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/fund_accounts/validations".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(validation) => Ok(validation),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        validation_id: &FundAccountValidationId,
+    ) -> RazorpayResult<FundAccountValidation> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/fund_accounts/validations/{}", validation_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(validation) => Ok(validation),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: ListFundAccountValidations<'_>,
+    ) -> RazorpayResult<Collection<FundAccountValidation>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/fund_accounts/validations".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(validations) => Ok(validations),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: ListFundAccountValidations<'a>,
+    ) -> impl Stream<Item = RazorpayResult<FundAccountValidation>> + 'a {
+        paginate(params.filter.clone(), move |filter| {
+            FundAccountValidation::list(
+                razorpay,
+                ListFundAccountValidations {
+                    filter: Some(filter),
+                    ..params.clone()
+                },
+            )
+        })
+    }
+}
@@ -157,15 +157,25 @@ def_id!(ItemId, "item_");
 def_id!(PlanId, "plan_");
 def_id!(AddonId, "ao_");
 def_id!(OrderId, "order_");
+def_id!(PaymentLinkId, "plink_");
+def_id!(QrCodeId, "qr_");
 def_id!(OfferId, "offer_");
 def_id!(BatchId, "batch_");
+def_id!(BankAccountId, "ba_");
+def_id!(BankTransferId, "bt_");
 def_id!(RefundId, "rfnd_");
+def_id!(ReversalId, "rvrsl_");
 def_id!(AccountId, "acc_");
 def_id!(AddressId, "addr_");
 def_id!(DisputeId, "disp_");
 def_id!(InvoiceId, "inv_");
 def_id!(PaymentId, "pay_");
 def_id!(CustomerId, "cust_");
+def_id!(ContactId, "cont_");
+def_id!(FundAccountId, "fa_");
+def_id!(FundAccountValidationId, "fav_");
+def_id!(PayoutId, "pout_");
+def_id!(PayoutLinkId, "poutlk_");
 def_id!(DowntimeId, "down_");
 def_id!(DocumentId, "doc_");
 def_id!(TransferId, "trf_");
@@ -173,5 +183,10 @@ def_id!(LineItemId, "li_");
 def_id!(AdjustmentId, "adj_");
 def_id!(SettlementId, "setl_");
 def_id!(SubscriptionId, "sub_");
+def_id!(TokenId, "token_");
+def_id!(ServiceProviderTokenId, "spt_");
+def_id!(UpiTransferId, "ut_");
+def_id!(VirtualAccountId, "va_");
+def_id!(VpaId, "vpa_");
 def_id!(InstantSettlementId, "setlod_");
 def_id!(InstantSettlementPayoutId, "setlodp_");
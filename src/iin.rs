@@ -59,6 +59,7 @@ impl Inn {
             .get(RequestParams {
                 url: format!("/inns/{}", inn_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -9,6 +9,7 @@ use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -19,14 +20,17 @@ use crate::{
     error::{InternalApiResult, RazorpayResult},
     ids::CustomerId,
     line_item::LineItem,
+    pagination::paginate,
     util::{deserialize_notes, serialize_bool_as_int_option},
-    Collection, InvoiceId, OrderId, PaymentId, Razorpay,
+    Collection, Filter, InvoiceId, OrderId, PaymentId, Razorpay,
 };
 
 #[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum InvoiceType {
     Invoice,
+    /// An authorization link of a recurring payment mandate.
+    Link,
 }
 
 #[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
@@ -68,8 +72,9 @@ pub struct Invoice {
     pub customer_id: Option<CustomerId>,
     pub customer_details: Option<CustomerDetails>,
     pub order_id: OrderId,
+    #[serde(default)]
     pub line_items: Vec<LineItem>,
-    pub payment_id: PaymentId,
+    pub payment_id: Option<PaymentId>,
     pub status: InvoiceStatus,
     #[serde(with = "ts_seconds")]
     pub expire_by: DateTime<Utc>,
@@ -227,9 +232,14 @@ pub struct UpdateInvoice<'a> {
 
 #[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
 pub struct ListInvoices<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_id: Option<&'a PaymentId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub receipt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub customer_id: Option<&'a CustomerId>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -266,6 +276,7 @@ impl Invoice {
             .post(RequestParams {
                 url: "/invoices".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -286,6 +297,7 @@ impl Invoice {
             .patch(RequestParams {
                 url: format!("/invoices/{}", invoice_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -305,6 +317,7 @@ impl Invoice {
             .post(RequestParams {
                 url: format!("/invoices/{}/issue", invoice_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -324,6 +337,7 @@ impl Invoice {
             .delete(RequestParams {
                 url: format!("/invoices/{}", invoice_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -343,6 +357,7 @@ impl Invoice {
             .post(RequestParams {
                 url: format!("/invoices/{}/cancel", invoice_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -362,6 +377,7 @@ impl Invoice {
             .get(RequestParams {
                 url: format!("/invoices/{}", invoice_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -381,6 +397,7 @@ impl Invoice {
             .get(RequestParams {
                 url: "/invoices".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params,
             })
             .await?;
@@ -391,6 +408,22 @@ impl Invoice {
         }
     }
 
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListInvoices<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<Invoice>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Invoice::list(
+                razorpay,
+                Some(ListInvoices {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
     pub async fn notify(
         razorpay: &Razorpay,
         invoice_id: &InvoiceId,
@@ -401,6 +434,7 @@ impl Invoice {
             .post(RequestParams {
                 url: format!("/invoices/{}/notify_by/{}", invoice_id, medium),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
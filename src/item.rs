@@ -5,6 +5,7 @@ use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -13,6 +14,7 @@ use crate::{
     common::{Collection, Currency, Filter},
     error::{InternalApiResult, RazorpayResult},
     ids::ItemId,
+    pagination::paginate,
     Razorpay,
 };
 
@@ -89,6 +91,7 @@ impl Item {
             .post(RequestParams {
                 url: "/items".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -108,6 +111,7 @@ impl Item {
             .get(RequestParams {
                 url: format!("/items/{}", item_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -130,6 +134,7 @@ impl Item {
             .get(RequestParams {
                 url: "/items".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -140,6 +145,25 @@ impl Item {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Item>> + '_
+    where
+        T: Into<Option<ListItems>>,
+    {
+        let params = params.into().unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Item::list(
+                razorpay,
+                ListItems {
+                    filter: Some(filter),
+                    ..params.clone()
+                },
+            )
+        })
+    }
+
     pub async fn update(
         razorpay: &Razorpay,
         item_id: &ItemId,
@@ -150,6 +174,7 @@ impl Item {
             .patch(RequestParams {
                 url: format!("/items/{}", item_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -169,6 +194,7 @@ impl Item {
             .delete(RequestParams {
                 url: format!("/items/{}", item_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -6,30 +6,53 @@ extern crate alloc;
 mod api;
 mod ids;
 mod razorpay;
+mod retry;
 
 pub mod account;
 pub mod addon;
 pub mod address;
 pub mod card;
 pub mod common;
+pub mod contact;
 pub mod customer;
 pub mod dispute;
+pub mod dispute_evidence;
+pub mod dispute_monitor;
 pub mod document;
 pub mod error;
+pub mod fund_account;
+pub mod fund_account_validation;
 pub mod iin;
 pub mod invoice;
 pub mod item;
 pub mod line_item;
 pub mod offer;
 pub mod order;
+pub mod pagination;
 pub mod payment;
+pub mod payment_link;
+pub mod payout;
+pub mod payout_link;
 pub mod plan;
+pub mod qr_code;
+pub mod recon;
+pub mod recurring;
 pub mod refund;
+pub mod s2s;
 pub mod settlement;
 pub mod subscription;
+pub mod token;
+pub mod transfer;
 pub mod util;
+pub mod virtual_account;
 pub mod webhook;
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+pub mod webhook_extract;
+pub mod webhook_router;
+#[cfg(feature = "std")]
+pub mod webhook_store;
 
 pub use common::{Collection, Country, Currency, Filter, Object};
 pub use ids::*;
-pub use razorpay::{Razorpay, VERSION};
+pub use razorpay::{Razorpay, RazorpayBuilder, VERSION};
+pub use retry::RetryPolicy;
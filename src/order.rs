@@ -1,7 +1,8 @@
 #[cfg(not(feature = "std"))]
-use alloc::{borrow::ToOwned, format, string::String};
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 
 use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -10,7 +11,9 @@ use crate::{
     common::{Collection, Currency, Filter, Object},
     error::{InternalApiResult, RazorpayResult},
     ids::OrderId,
+    pagination::paginate,
     payment::Payment,
+    transfer::CreateTransfer,
     util::{deserialize_notes, serialize_bool_as_int_option},
     OfferId, Razorpay,
 };
@@ -34,6 +37,11 @@ pub struct CreateOrder<'a> {
     pub partial_payment: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bank_account: Option<OrderBankAccount<'a>>,
+    /// Transfers to linked accounts, made once the order is paid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfers: Option<Vec<CreateTransfer<'a>>>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq, Eq)]
@@ -98,6 +106,7 @@ impl Order {
             .post(RequestParams {
                 url: "/orders".to_owned(),
                 version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
                 data: Some(params),
             })
             .await?;
@@ -117,6 +126,7 @@ impl Order {
             .get(RequestParams {
                 url: "/orders".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params,
             })
             .await?;
@@ -127,6 +137,22 @@ impl Order {
         }
     }
 
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListOrders<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<Order>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Order::list(
+                razorpay,
+                Some(ListOrders {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         order_id: &OrderId,
@@ -136,6 +162,7 @@ impl Order {
             .get(RequestParams {
                 url: format!("/orders/{}", order_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -155,6 +182,7 @@ impl Order {
             .get(RequestParams {
                 url: format!("/orders/{}/payments", order_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -175,6 +203,7 @@ impl Order {
             .patch(RequestParams {
                 url: format!("/orders/{}", order_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({ "notes": notes })),
             })
             .await?;
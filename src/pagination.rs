@@ -0,0 +1,231 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::{IntoIter, Vec};
+#[cfg(not(feature = "std"))]
+use core::future::Future;
+#[cfg(feature = "std")]
+use std::{future::Future, vec::IntoIter};
+
+use futures_core::Stream;
+use futures_util::{pin_mut, stream, StreamExt};
+
+use crate::{
+    common::{Collection, Filter},
+    error::RazorpayResult,
+};
+
+/// The largest page size accepted by the Razorpay API.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+struct Pages<T, F> {
+    fetch: F,
+    filter: Filter,
+    items: IntoIter<T>,
+    done: bool,
+}
+
+/// Turns a function fetching a single page into a stream of all items,
+/// starting from `filter.skip` and requesting `filter.count` items per page.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    filter: Option<Filter>,
+    fetch: F,
+) -> impl Stream<Item = RazorpayResult<T>> + 'a
+where
+    T: 'a,
+    F: FnMut(Filter) -> Fut + 'a,
+    Fut: Future<Output = RazorpayResult<Collection<T>>> + 'a,
+{
+    let mut filter = filter.unwrap_or_default();
+    filter.count = Some(
+        filter
+            .count
+            .unwrap_or(MAX_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE),
+    );
+    filter.skip = Some(filter.skip.unwrap_or_default());
+
+    let pages = Pages {
+        fetch,
+        filter,
+        items: Vec::new().into_iter(),
+        done: false,
+    };
+
+    stream::unfold(pages, |mut pages| async move {
+        loop {
+            if let Some(item) = pages.items.next() {
+                return Some((Ok(item), pages));
+            }
+
+            if pages.done {
+                return None;
+            }
+
+            match (pages.fetch)(pages.filter.clone()).await {
+                Ok(page) => {
+                    let fetched = page.items.len();
+                    // a short page is the last one
+                    pages.done = fetched < pages.filter.count.unwrap_or(0);
+                    pages.filter.skip =
+                        pages.filter.skip.map(|skip| skip + fetched);
+                    pages.items = page.items.into_iter();
+                }
+                Err(error) => {
+                    pages.done = true;
+                    return Some((Err(error), pages));
+                }
+            }
+        }
+    })
+}
+
+/// Collects the items of a `list_stream` into a `Vec`, stopping after
+/// `limit` items if given or at the first error.
+pub async fn collect_all<S, T>(
+    stream: S,
+    limit: Option<usize>,
+) -> RazorpayResult<Vec<T>>
+where
+    S: Stream<Item = RazorpayResult<T>>,
+{
+    pin_mut!(stream);
+
+    let mut items = Vec::new();
+    while limit.map_or(true, |limit| items.len() < limit) {
+        match stream.next().await {
+            Some(item) => items.push(item?),
+            None => break,
+        }
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        future::{ready, Ready},
+        rc::Rc,
+    };
+
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::error::RazorpayError;
+
+    type Page = Ready<RazorpayResult<Collection<usize>>>;
+    type Requests = Rc<RefCell<Vec<Filter>>>;
+
+    // pages through the numbers `0..total`, recording every filter asked for
+    // and failing the page starting at `fail_at`
+    fn numbers(
+        total: usize,
+        fail_at: Option<usize>,
+    ) -> (impl FnMut(Filter) -> Page, Requests) {
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let recorded = requests.clone();
+
+        let fetch = move |filter: Filter| {
+            recorded.borrow_mut().push(filter.clone());
+            let skip = filter.skip.unwrap();
+
+            if fail_at == Some(skip) {
+                return ready(Err(RazorpayError::HttpError {
+                    status: StatusCode::SERVICE_UNAVAILABLE,
+                    body: String::new(),
+                    retry_after: None,
+                }));
+            }
+
+            let items: Vec<usize> =
+                (skip..total).take(filter.count.unwrap()).collect();
+            ready(Ok(Collection {
+                count: items.len(),
+                items,
+            }))
+        };
+
+        (fetch, requests)
+    }
+
+    fn skips(requests: &Requests) -> Vec<usize> {
+        requests.borrow().iter().map(|f| f.skip.unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn advances_skip_until_a_short_page() {
+        let (fetch, requests) = numbers(250, None);
+
+        let items = collect_all(paginate(None, fetch), None).await.unwrap();
+
+        assert_eq!(items, (0..250).collect::<Vec<_>>());
+        assert_eq!(skips(&requests), [0, 100, 200]);
+    }
+
+    #[tokio::test]
+    async fn ends_on_an_empty_page_after_full_ones() {
+        let (fetch, requests) = numbers(20, None);
+        let filter = Filter {
+            count: Some(10),
+            ..Default::default()
+        };
+
+        let items = collect_all(paginate(Some(filter), fetch), None)
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 20);
+        assert_eq!(skips(&requests), [0, 10, 20]);
+    }
+
+    #[tokio::test]
+    async fn clamps_the_page_size() {
+        for (count, expected) in
+            [(None, 100), (Some(0), 1), (Some(25), 25), (Some(500), 100)]
+        {
+            let (fetch, requests) = numbers(0, None);
+            let filter = Filter {
+                count,
+                skip: Some(7),
+                ..Default::default()
+            };
+
+            collect_all(paginate(Some(filter), fetch), None)
+                .await
+                .unwrap();
+
+            let requests = requests.borrow();
+            assert_eq!(requests[0].count, Some(expected), "{:?}", count);
+            assert_eq!(requests[0].skip, Some(7));
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_after_an_error() {
+        let (fetch, requests) = numbers(250, Some(100));
+        let stream = paginate(None, fetch);
+        pin_mut!(stream);
+
+        for expected in 0..100 {
+            assert_eq!(stream.next().await.unwrap().unwrap(), expected);
+        }
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+        assert_eq!(skips(&requests), [0, 100]);
+    }
+
+    #[tokio::test]
+    async fn collects_up_to_the_limit() {
+        let (fetch, requests) = numbers(250, None);
+
+        let items =
+            collect_all(paginate(None, fetch), Some(150)).await.unwrap();
+
+        assert_eq!(items, (0..150).collect::<Vec<_>>());
+        // pages past the limit are never fetched
+        assert_eq!(skips(&requests), [0, 100]);
+
+        let (fetch, _) = numbers(250, Some(200));
+        assert!(collect_all(paginate(None, fetch), None).await.is_err());
+    }
+}
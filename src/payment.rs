@@ -5,6 +5,7 @@ use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -13,8 +14,11 @@ use crate::{
     card::{Card, CardType},
     common::{Collection, Currency, Filter, Object},
     error::{InternalApiResult, RazorpayResult},
-    ids::{CardId, DowntimeId, OrderId, PaymentId, RefundId},
+    ids::{
+        CardId, CustomerId, DowntimeId, OrderId, PaymentId, RefundId, TokenId,
+    },
     offer::Offer,
+    pagination::paginate,
     refund::{CreateRefund, Refund},
     util::deserialize_notes,
     Razorpay,
@@ -38,6 +42,11 @@ pub enum PaymentMethod {
     Wallet,
     Emi,
     Upi,
+    Emandate,
+    Nach,
+    /// A bank transfer to a virtual account.
+    #[serde(rename = "bank_transfer")]
+    BankTransfer,
 }
 
 #[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
@@ -49,7 +58,8 @@ pub enum PaymentRefundStatus {
 
 #[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
 pub struct PaymentAcquirerData {
-    pub rrn: String,
+    /// Only set for UPI and some card payments.
+    pub rrn: Option<String>,
     pub authentication_reference_number: Option<String>,
     pub bank_transaction_id: Option<String>,
     pub auth_code: Option<String>,
@@ -91,7 +101,12 @@ pub struct Payment {
     pub currency: Currency,
     pub status: PaymentStatus,
     pub method: PaymentMethod,
-    pub order_id: OrderId,
+    /// Unset for payments made without an order, such as those made to a
+    /// virtual account.
+    pub order_id: Option<OrderId>,
+    pub customer_id: Option<CustomerId>,
+    /// The saved card or mandate the payment was made with.
+    pub token_id: Option<TokenId>,
     pub description: Option<String>,
     pub international: bool,
     pub refund_status: Option<PaymentRefundStatus>,
@@ -121,9 +136,11 @@ pub struct Payment {
 }
 
 #[derive(Debug, Serialize, Clone, Eq, PartialEq)]
-pub struct CapturePayment {
+pub struct CapturePayment<'a> {
     pub amount: u64,
     pub currency: Currency,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
 }
 
 #[derive(Debug, Serialize, Clone, Eq, PartialEq)]
@@ -262,13 +279,14 @@ impl Payment {
     pub async fn capture(
         razorpay: &Razorpay,
         payment_id: &PaymentId,
-        params: CapturePayment,
+        params: CapturePayment<'_>,
     ) -> RazorpayResult<Payment> {
         let res = razorpay
             .api
             .post(RequestParams {
                 url: format!("/payments/{}/capture", payment_id),
                 version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
                 data: Some(params),
             })
             .await?;
@@ -289,6 +307,7 @@ impl Payment {
             .get(RequestParams {
                 url: format!("/payments/{}", payment_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({
                     "expand[]": expand,
                 })),
@@ -310,6 +329,7 @@ impl Payment {
             .get(RequestParams {
                 url: "/payments".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params,
             })
             .await?;
@@ -320,6 +340,22 @@ impl Payment {
         }
     }
 
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListPayments<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<Payment>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Payment::list(
+                razorpay,
+                Some(ListPayments {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
     pub async fn fetch_card(
         razorpay: &Razorpay,
         payment_id: &PaymentId,
@@ -329,6 +365,7 @@ impl Payment {
             .get(RequestParams {
                 url: format!("/payments/{}/card", payment_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -349,6 +386,7 @@ impl Payment {
             .patch(RequestParams {
                 url: format!("/payments/{}", payment_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({
                     "notes": notes,
                 })),
@@ -371,6 +409,7 @@ impl Payment {
             .post(RequestParams {
                 url: format!("/payments/{}/refund", payment_id),
                 version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
                 data: Some(params),
             })
             .await?;
@@ -394,6 +433,7 @@ impl Payment {
             .get(RequestParams {
                 url: format!("/payments/{}/refunds", payment_id),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -404,6 +444,19 @@ impl Payment {
         }
     }
 
+    pub fn list_refunds_stream<'a, T>(
+        razorpay: &'a Razorpay,
+        payment_id: &'a PaymentId,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Refund>> + 'a
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| {
+            Payment::list_refunds(razorpay, payment_id, filter)
+        })
+    }
+
     pub async fn fetch_refund(
         razorpay: &Razorpay,
         payment_id: &PaymentId,
@@ -414,6 +467,7 @@ impl Payment {
             .get(RequestParams {
                 url: format!("/payments/{}/refunds{}", payment_id, refund_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -434,6 +488,7 @@ impl Downtime {
             .get(RequestParams {
                 url: "/payments/downtimes".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -453,6 +508,7 @@ impl Downtime {
             .get(RequestParams {
                 url: format!("/payments/downtimes/{}", downtime_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
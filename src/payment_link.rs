@@ -0,0 +1,357 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::{Display, Formatter, Result as FormatterResult};
+#[cfg(feature = "std")]
+use std::fmt::{Display, Formatter, Result as FormatterResult};
+
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RequestParams,
+    common::{Currency, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::{OrderId, PaymentId, PaymentLinkId},
+    payment::{PaymentMethod, PaymentStatus},
+    util::{
+        deserialize_empty_array_as_none, deserialize_notes,
+        deserialize_ts_seconds_or_zero,
+    },
+    Razorpay,
+};
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentLinkStatus {
+    Created,
+    PartiallyPaid,
+    Expired,
+    Cancelled,
+    Paid,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PaymentLinkCustomer {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub contact: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PaymentLinkNotify {
+    pub sms: bool,
+    pub email: bool,
+    #[serde(default)]
+    pub whatsapp: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PaymentLinkReminders {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PaymentLinkPayment {
+    pub payment_id: PaymentId,
+    pub plink_id: PaymentLinkId,
+    pub amount: u64,
+    pub method: PaymentMethod,
+    pub status: PaymentStatus,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+// unlike other entities, payment links are sent without an `entity` field,
+// so this can't be `#[serde(tag = "entity")]` and is told apart from the
+// other webhook entities by its fields alone
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PaymentLink {
+    pub id: PaymentLinkId,
+    pub amount: u64,
+    pub amount_paid: u64,
+    pub currency: Currency,
+    pub accept_partial: bool,
+    pub first_min_partial_amount: Option<u64>,
+    pub description: Option<String>,
+    pub reference_id: Option<String>,
+    pub customer: Option<PaymentLinkCustomer>,
+    pub notify: Option<PaymentLinkNotify>,
+    pub reminder_enable: bool,
+    #[serde(default, deserialize_with = "deserialize_empty_array_as_none")]
+    pub reminders: Option<PaymentLinkReminders>,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    pub callback_url: Option<String>,
+    pub callback_method: Option<String>,
+    pub short_url: String,
+    pub status: PaymentLinkStatus,
+    #[serde(default)]
+    pub upi_link: bool,
+    #[serde(default)]
+    pub whatsapp_link: bool,
+    pub order_id: Option<OrderId>,
+    pub payments: Option<Vec<PaymentLinkPayment>>,
+    pub user_id: Option<String>,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "deserialize_ts_seconds_or_zero")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_ts_seconds_or_zero")]
+    pub expire_by: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_ts_seconds_or_zero")]
+    pub expired_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_ts_seconds_or_zero")]
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct CreatePaymentLinkCustomer<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct CreatePaymentLinkNotify {
+    pub sms: bool,
+    pub email: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentLinkCallbackMethod {
+    Get,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct CreatePaymentLink<'a> {
+    pub amount: u64,
+    pub currency: Currency,
+    /// Creates a UPI payment link, only [`Currency::INR`] is supported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upi_link: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_partial: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_min_partial_amount: Option<u64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub expire_by: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<CreatePaymentLinkCustomer<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify: Option<CreatePaymentLinkNotify>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminder_enable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_method: Option<PaymentLinkCallbackMethod>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct UpdatePaymentLink<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_partial: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub expire_by: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminder_enable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct ListPaymentLinks<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<&'a PaymentId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PaymentLinkNotifyMedium {
+    Sms,
+    Email,
+}
+
+impl Display for PaymentLinkNotifyMedium {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        write!(
+            f,
+            "{}",
+            match self {
+                PaymentLinkNotifyMedium::Sms => "sms",
+                PaymentLinkNotifyMedium::Email => "email",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PaymentLinkList {
+    payment_links: Vec<PaymentLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PaymentLinkNotifyResult {
+    success: bool,
+}
+
+impl PaymentLink {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreatePaymentLink<'_>,
+    ) -> RazorpayResult<PaymentLink> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payment_links".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment_link) => Ok(payment_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        payment_link_id: &PaymentLinkId,
+    ) -> RazorpayResult<PaymentLink> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payment_links/{}", payment_link_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment_link) => Ok(payment_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Lists payment links, unlike most list APIs this one is not paginated.
+    pub async fn list<'a, T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> RazorpayResult<Vec<PaymentLink>>
+    where
+        T: Into<Option<ListPaymentLinks<'a>>>,
+    {
+        let res: InternalApiResult<PaymentLinkList> = razorpay
+            .api
+            .get(RequestParams {
+                url: "/payment_links".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params.into(),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(res) => Ok(res.payment_links),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn update(
+        razorpay: &Razorpay,
+        payment_link_id: &PaymentLinkId,
+        params: UpdatePaymentLink<'_>,
+    ) -> RazorpayResult<PaymentLink> {
+        let res = razorpay
+            .api
+            .patch(RequestParams {
+                url: format!("/payment_links/{}", payment_link_id),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment_link) => Ok(payment_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn cancel(
+        razorpay: &Razorpay,
+        payment_link_id: &PaymentLinkId,
+        idempotency_key: Option<&str>,
+    ) -> RazorpayResult<PaymentLink> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payment_links/{}/cancel", payment_link_id),
+                version: None,
+                idempotency_key: idempotency_key.map(Into::into),
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment_link) => Ok(payment_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn notify(
+        razorpay: &Razorpay,
+        payment_link_id: &PaymentLinkId,
+        medium: PaymentLinkNotifyMedium,
+    ) -> RazorpayResult<bool> {
+        let res: InternalApiResult<PaymentLinkNotifyResult> = razorpay
+            .api
+            .post(RequestParams {
+                url: format!(
+                    "/payment_links/{}/notify_by/{}",
+                    payment_link_id, medium
+                ),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(res) => Ok(res.success),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
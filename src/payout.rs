@@ -0,0 +1,229 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+
+use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Currency, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    fund_account::FundAccount,
+    ids::PayoutId,
+    pagination::paginate,
+    retry::IdempotencyKey,
+    util::{deserialize_notes, generate_idempotency_key},
+    BatchId, ContactId, FundAccountId, Razorpay,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub enum PayoutMode {
+    #[serde(rename = "NEFT")]
+    Neft,
+    #[serde(rename = "RTGS")]
+    Rtgs,
+    #[serde(rename = "IMPS")]
+    Imps,
+    #[serde(rename = "UPI")]
+    Upi,
+    #[serde(rename = "card")]
+    Card,
+    #[serde(rename = "amazonpay")]
+    AmazonPay,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutStatus {
+    Queued,
+    Pending,
+    Rejected,
+    Processing,
+    Processed,
+    Cancelled,
+    Reversed,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PayoutStatusDetails {
+    pub reason: Option<String>,
+    pub description: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(tag = "entity", rename = "payout")]
+pub struct Payout {
+    pub id: PayoutId,
+    pub fund_account_id: FundAccountId,
+    /// Only present when the fund account was created with the payout.
+    pub fund_account: Option<FundAccount>,
+    pub amount: u64,
+    pub currency: Currency,
+    pub fees: u64,
+    pub tax: u64,
+    pub status: PayoutStatus,
+    pub status_details: Option<PayoutStatusDetails>,
+    /// One of `refund`, `cashback`, `payout`, `salary`, `utility bill`,
+    /// `vendor bill` or a custom purpose.
+    pub purpose: String,
+    pub mode: PayoutMode,
+    pub utr: Option<String>,
+    pub reference_id: Option<String>,
+    pub narration: Option<String>,
+    pub batch_id: Option<BatchId>,
+    pub failure_reason: Option<String>,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreatePayout<'a> {
+    /// The RazorpayX account number the payout is made from.
+    pub account_number: &'a str,
+    pub fund_account_id: &'a FundAccountId,
+    pub amount: u64,
+    pub currency: Currency,
+    pub mode: PayoutMode,
+    pub purpose: &'a str,
+    /// Queues the payout instead of failing it when the balance is low.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_if_low_balance: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub narration: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    /// Sent as `X-Payout-Idempotency`, a random key is generated if unset.
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct ListPayouts<'a> {
+    pub account_number: &'a str,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<&'a ContactId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fund_account_id: Option<&'a FundAccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<PayoutMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PayoutStatus>,
+}
+
+impl Payout {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreatePayout<'_>,
+    ) -> RazorpayResult<Payout> {
+        // the payouts API rejects requests without an idempotency key
+        let idempotency_key = params
+            .idempotency_key
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(generate_idempotency_key);
+
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payouts".to_owned(),
+                version: None,
+                idempotency_key: Some(IdempotencyKey::Payout(idempotency_key)),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout) => Ok(payout),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        payout_id: &PayoutId,
+    ) -> RazorpayResult<Payout> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payouts/{}", payout_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout) => Ok(payout),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: ListPayouts<'_>,
+    ) -> RazorpayResult<Collection<Payout>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/payouts".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payouts) => Ok(payouts),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: ListPayouts<'a>,
+    ) -> impl Stream<Item = RazorpayResult<Payout>> + 'a {
+        paginate(params.filter.clone(), move |filter| {
+            Payout::list(
+                razorpay,
+                ListPayouts {
+                    filter: Some(filter),
+                    ..params.clone()
+                },
+            )
+        })
+    }
+
+    /// Cancels a payout, only payouts in the `queued` state can be
+    /// cancelled.
+    pub async fn cancel(
+        razorpay: &Razorpay,
+        payout_id: &PayoutId,
+        idempotency_key: Option<&str>,
+    ) -> RazorpayResult<Payout> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payouts/{}/cancel", payout_id),
+                version: None,
+                idempotency_key: idempotency_key
+                    .map(|key| IdempotencyKey::Payout(key.to_owned())),
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout) => Ok(payout),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
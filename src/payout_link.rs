@@ -0,0 +1,243 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Currency, Filter, Object},
+    contact::ContactType,
+    error::{InternalApiResult, RazorpayResult},
+    ids::PayoutLinkId,
+    pagination::paginate,
+    retry::IdempotencyKey,
+    util::{deserialize_notes, generate_idempotency_key},
+    ContactId, FundAccountId, Razorpay,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutLinkStatus {
+    Pending,
+    Issued,
+    Processing,
+    Processed,
+    Attempted,
+    Cancelled,
+    Rejected,
+    Expired,
+}
+
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+pub struct PayoutLinkContact {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub contact: Option<String>,
+}
+
+/// A link sent to a contact to collect their bank account or VPA and pay
+/// out to it.
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(tag = "entity", rename = "payout_link")]
+pub struct PayoutLink {
+    pub id: PayoutLinkId,
+    pub contact_id: ContactId,
+    pub contact: Option<PayoutLinkContact>,
+    /// Set once the contact has provided their account details.
+    pub fund_account_id: Option<FundAccountId>,
+    pub amount: u64,
+    pub currency: Currency,
+    pub purpose: String,
+    pub description: Option<String>,
+    pub receipt: Option<String>,
+    pub status: PayoutLinkStatus,
+    pub short_url: String,
+    pub send_sms: bool,
+    pub send_email: bool,
+    #[serde(default)]
+    pub attempt_count: u32,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub expire_by: Option<DateTime<Utc>>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub expired_at: Option<DateTime<Utc>>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct CreatePayoutLinkContact<'a> {
+    /// An existing contact, the other fields create a new one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<&'a ContactId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<&'a str>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<ContactType>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct CreatePayoutLink<'a> {
+    /// The RazorpayX account number the payout is made from.
+    pub account_number: &'a str,
+    pub contact: CreatePayoutLinkContact<'a>,
+    pub amount: u64,
+    pub currency: Currency,
+    pub purpose: &'a str,
+    pub send_sms: bool,
+    pub send_email: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<&'a str>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub expire_by: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    /// Sent as `X-Payout-Idempotency`, a random key is generated if unset.
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Eq, PartialEq)]
+pub struct ListPayoutLinks<'a> {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<&'a ContactId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_phone_number: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_email: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fund_account_id: Option<&'a FundAccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PayoutLinkStatus>,
+}
+
+impl PayoutLink {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreatePayoutLink<'_>,
+    ) -> RazorpayResult<PayoutLink> {
+        // like payouts, a link sent twice would pay the contact twice
+        let idempotency_key = params
+            .idempotency_key
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(generate_idempotency_key);
+
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payout-links".to_owned(),
+                version: None,
+                idempotency_key: Some(IdempotencyKey::Payout(idempotency_key)),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout_link) => Ok(payout_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        payout_link_id: &PayoutLinkId,
+    ) -> RazorpayResult<PayoutLink> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payout-links/{}", payout_link_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout_link) => Ok(payout_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: Option<ListPayoutLinks<'_>>,
+    ) -> RazorpayResult<Collection<PayoutLink>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/payout-links".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout_links) => Ok(payout_links),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListPayoutLinks<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<PayoutLink>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            PayoutLink::list(
+                razorpay,
+                Some(ListPayoutLinks {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
+    /// Cancels a payout link, only links in the `issued` state can be
+    /// cancelled.
+    pub async fn cancel(
+        razorpay: &Razorpay,
+        payout_link_id: &PayoutLinkId,
+        idempotency_key: Option<&str>,
+    ) -> RazorpayResult<PayoutLink> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payout-links/{}/cancel", payout_link_id),
+                version: None,
+                idempotency_key: idempotency_key
+                    .map(|key| IdempotencyKey::Payout(key.to_owned())),
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payout_link) => Ok(payout_link),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
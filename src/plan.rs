@@ -2,6 +2,7 @@
 use alloc::{borrow::ToOwned, format};
 
 use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -10,6 +11,7 @@ use crate::{
     error::{InternalApiResult, RazorpayResult},
     ids::PlanId,
     item::Item,
+    pagination::paginate,
     util::deserialize_notes,
     Razorpay,
 };
@@ -75,6 +77,7 @@ impl Plan {
             .post(crate::api::RequestParams {
                 url: "/plans".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -97,6 +100,7 @@ impl Plan {
             .get(RequestParams {
                 url: "/plans".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -107,6 +111,16 @@ impl Plan {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Plan>> + '_
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| Plan::list(razorpay, filter))
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         plan_id: &PlanId,
@@ -116,6 +130,7 @@ impl Plan {
             .get(RequestParams {
                 url: format!("/plans/{}", plan_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
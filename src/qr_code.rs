@@ -0,0 +1,237 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::QrCodeId,
+    pagination::paginate,
+    payment::Payment,
+    util::deserialize_notes,
+    CustomerId, PaymentId, Razorpay,
+};
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QrCodeType {
+    #[default]
+    UpiQr,
+    BharatQr,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QrCodeUsage {
+    #[default]
+    SingleUse,
+    MultipleUse,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QrCodeStatus {
+    Active,
+    Closed,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QrCodeCloseReason {
+    OnDemand,
+    Paid,
+    Expired,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "qr_code")]
+pub struct QrCode {
+    pub id: QrCodeId,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: QrCodeType,
+    pub usage: QrCodeUsage,
+    pub image_url: String,
+    pub fixed_amount: bool,
+    pub payment_amount: Option<u64>,
+    pub payments_amount_received: u64,
+    pub payments_count_received: u64,
+    pub status: QrCodeStatus,
+    pub description: Option<String>,
+    pub customer_id: Option<CustomerId>,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub close_by: Option<DateTime<Utc>>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub closed_at: Option<DateTime<Utc>>,
+    pub close_reason: Option<QrCodeCloseReason>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateQrCode<'a> {
+    #[serde(rename = "type")]
+    pub type_: QrCodeType,
+    pub usage: QrCodeUsage,
+    /// Accepts only `payment_amount` when set, any amount otherwise.
+    pub fixed_amount: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub close_by: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct ListQrCodes<'a> {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_id: Option<&'a PaymentId>,
+}
+
+impl QrCode {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateQrCode<'_>,
+    ) -> RazorpayResult<QrCode> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payments/qr_codes".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(qr_code) => Ok(qr_code),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        qr_code_id: &QrCodeId,
+    ) -> RazorpayResult<QrCode> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payments/qr_codes/{}", qr_code_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(qr_code) => Ok(qr_code),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: Option<ListQrCodes<'_>>,
+    ) -> RazorpayResult<Collection<QrCode>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/payments/qr_codes".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(qr_codes) => Ok(qr_codes),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListQrCodes<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<QrCode>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            QrCode::list(
+                razorpay,
+                Some(ListQrCodes {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
+    pub async fn list_payments<T>(
+        razorpay: &Razorpay,
+        qr_code_id: &QrCodeId,
+        params: T,
+    ) -> RazorpayResult<Collection<Payment>>
+    where
+        T: Into<Option<Filter>>,
+    {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payments/qr_codes/{}/payments", qr_code_id),
+                version: None,
+                idempotency_key: None,
+                data: params.into(),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payments) => Ok(payments),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn close(
+        razorpay: &Razorpay,
+        qr_code_id: &QrCodeId,
+        idempotency_key: Option<&str>,
+    ) -> RazorpayResult<QrCode> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payments/qr_codes/{}/close", qr_code_id),
+                version: None,
+                idempotency_key: idempotency_key.map(Into::into),
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(qr_code) => Ok(qr_code),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
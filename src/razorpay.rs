@@ -1,28 +1,281 @@
 #[cfg(not(feature = "std"))]
 use alloc::{borrow::ToOwned, format, string::String};
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Client, ClientBuilder, Proxy,
+};
 
-use crate::api::{Api, ApiOptions};
+use crate::{
+    api::{Api, ApiOptions},
+    error::RazorpayResult,
+    retry::RetryPolicy,
+};
 
 const BASE_URL: &str = "https://api.razorpay.com";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// sent by the clients the builder creates
+fn default_user_agent() -> String {
+    format!("rusty-razorpay@{}", VERSION)
+}
+
 #[derive(Debug)]
 pub struct Razorpay {
     pub(crate) api: Api,
 }
 
 impl Razorpay {
+    /// Creates a client with the default configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client cannot be initialized, use
+    /// [`Razorpay::builder`] to handle that error instead.
+    pub fn new(
+        key_id: impl Into<String>,
+        key_secret: impl Into<String>,
+    ) -> Self {
+        Self::builder(key_id, key_secret)
+            .build()
+            .expect("failed to initialize the HTTP client")
+    }
+
+    pub fn builder(
+        key_id: impl Into<String>,
+        key_secret: impl Into<String>,
+    ) -> RazorpayBuilder {
+        RazorpayBuilder::new(key_id, key_secret)
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.api.retry_policy = retry_policy;
+        self
+    }
+
+    /// Attach a generated idempotency key to every `POST` and `PATCH`
+    /// request that doesn't already carry one, which also makes them safe
+    /// to retry.
+    pub fn with_auto_idempotency_keys(mut self, enabled: bool) -> Self {
+        self.api.auto_idempotency_keys = enabled;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct RazorpayBuilder {
+    key_id: String,
+    key_secret: String,
+    base_url: String,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    default_headers: HeaderMap,
+    client: Option<Client>,
+    retry_policy: RetryPolicy,
+    auto_idempotency_keys: bool,
+}
+
+impl RazorpayBuilder {
     pub fn new(
         key_id: impl Into<String>,
         key_secret: impl Into<String>,
     ) -> Self {
         Self {
+            key_id: key_id.into(),
+            key_secret: key_secret.into(),
+            base_url: BASE_URL.to_owned(),
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            proxy: None,
+            default_headers: HeaderMap::new(),
+            client: None,
+            retry_policy: RetryPolicy::default(),
+            auto_idempotency_keys: false,
+        }
+    }
+
+    /// The URL requests are sent to, without the API version, e.g.
+    /// `http://localhost:8080` for a local mock.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for a whole request, from connecting until the response body
+    /// has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// A header sent with every request, replacing any previous value.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Use a pre-built client, the timeouts, proxy and `rusty-razorpay`
+    /// `User-Agent` of this builder are ignored in that case.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// See [`Razorpay::with_auto_idempotency_keys`].
+    pub fn auto_idempotency_keys(mut self, enabled: bool) -> Self {
+        self.auto_idempotency_keys = enabled;
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn build(self) -> RazorpayResult<Razorpay> {
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder =
+                    ClientBuilder::new().user_agent(default_user_agent());
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.read_timeout {
+                    builder = builder.read_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(Razorpay {
             api: Api::new(ApiOptions {
-                base_url: BASE_URL.to_owned(),
-                user_agent: format!("rusty-razorpay@{}", VERSION),
-                key_id: key_id.into(),
-                key_secret: key_secret.into(),
+                base_url: self.base_url,
+                key_id: self.key_id,
+                key_secret: self.key_secret,
+                client,
+                default_headers: self.default_headers,
+                retry_policy: self.retry_policy,
+                auto_idempotency_keys: self.auto_idempotency_keys,
             }),
-        }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::USER_AGENT;
+    use serde_json::Value;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::api::RequestParams;
+
+    // sends a request to a local server and returns its `User-Agent`
+    async fn user_agent(builder: RazorpayBuilder) -> Option<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                      content-length: 2\r\nconnection: close\r\n\r\n{}",
+                )
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let razorpay = builder.base_url(base_url).build().unwrap();
+        let _: Value = razorpay
+            .api
+            .get(RequestParams::<()> {
+                url: "/orders".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: None,
+            })
+            .await
+            .unwrap();
+
+        server.await.unwrap().lines().find_map(|line| {
+            let (name, value) = line.split_once(": ")?;
+            name.eq_ignore_ascii_case(USER_AGENT.as_str())
+                .then(|| value.to_owned())
+        })
+    }
+
+    #[tokio::test]
+    async fn sends_the_default_user_agent() {
+        assert_eq!(
+            user_agent(Razorpay::builder("key", "secret"))
+                .await
+                .unwrap(),
+            default_user_agent()
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_the_user_agent_of_a_custom_client() {
+        let client =
+            Client::builder().user_agent("my-app/1.0").build().unwrap();
+        assert_eq!(
+            user_agent(Razorpay::builder("key", "secret").client(client))
+                .await
+                .unwrap(),
+            "my-app/1.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn prefers_a_user_agent_header() {
+        let builder = Razorpay::builder("key", "secret")
+            .header(USER_AGENT, HeaderValue::from_static("my-app/2.0"));
+        assert_eq!(user_agent(builder).await.unwrap(), "my-app/2.0");
     }
 }
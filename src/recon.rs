@@ -0,0 +1,841 @@
+//! Reconciling the rows of [`SettlementRecon::fetch`] against the merchant's
+//! own ledger of payments and refunds, without calling the API.
+//!
+//! ```
+//! # use rusty_razorpay::{
+//! #     recon::{LedgerEntry, ReconStatus, Reconciler},
+//! #     settlement::SettlementRecon,
+//! # };
+//! let rows: Vec<SettlementRecon> = serde_json::from_str(
+//!     r#"[{
+//!         "entity_id": "pay_DEXrnipqTmWVGE", "type": "payment",
+//!         "debit": 0, "credit": 97100, "amount": 100000, "currency": "INR",
+//!         "fee": 2900, "tax": 442, "on_hold": false, "settled": true,
+//!         "created_at": 1567692556, "settled_at": 1568176960,
+//!         "settlement_id": "setl_DGlQ1Rj8os78Ec", "description": null,
+//!         "notes": [], "payment_id": null,
+//!         "settlement_utr": "1568176960vxp0rj", "order_id": "order_DEXrnRiR3SNDHA",
+//!         "order_receipt": null, "method": "card", "card_network": "MasterCard",
+//!         "card_issuer": "KARB", "card_type": "debit", "dispute_id": null
+//!     }]"#,
+//! )
+//! .unwrap();
+//! let ledger: Vec<LedgerEntry> = serde_json::from_str(
+//!     r#"[{ "kind": "payment", "order_id": "order_DEXrnRiR3SNDHA",
+//!           "amount": 100000, "currency": "INR", "reference": "INV-1042" }]"#,
+//! )
+//! .unwrap();
+//!
+//! let report = Reconciler::new().reconcile(&rows, &ledger, &[]);
+//! assert_eq!(report.items[0].status, ReconStatus::Matched);
+//! assert_eq!(report.settlements[0].net, 97100);
+//! ```
+//!
+//! [`SettlementRecon::fetch`]: crate::settlement::SettlementRecon::fetch
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::Currency,
+    settlement::{
+        Settlement, SettlementRecon, SettlementReconEntityId, SettlementType,
+    },
+    OrderId, PaymentId, RefundId, SettlementId,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LedgerEntryKind {
+    Payment,
+    Refund,
+}
+
+/// A payment or refund as recorded by the merchant, matched to a recon row
+/// by its Razorpay id, then its order id and then its order receipt. Among
+/// the entries of an order, or the refunds of a payment, the one with the
+/// amount of the row is preferred.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub kind: LedgerEntryKind,
+    #[serde(default)]
+    pub payment_id: Option<PaymentId>,
+    #[serde(default)]
+    pub refund_id: Option<RefundId>,
+    #[serde(default)]
+    pub order_id: Option<OrderId>,
+    #[serde(default)]
+    pub order_receipt: Option<String>,
+    /// The amount paid or refunded, before fees.
+    pub amount: u64,
+    pub currency: Currency,
+    /// The fee, tax included, agreed with Razorpay for this entry.
+    #[serde(default)]
+    pub expected_fee: Option<u64>,
+    /// The merchant's own id of the entry, copied to the report.
+    #[serde(default)]
+    pub reference: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconStatus {
+    /// The row matches its ledger entry.
+    Matched,
+    /// A payment or refund row with no ledger entry.
+    MissingInLedger,
+    /// A ledger entry with no row.
+    MissingInRecon,
+    /// The amount or the currency of the row and its ledger entry differ.
+    AmountMismatch,
+    /// The fee differs from the expected one, exceeds
+    /// [`Reconciler::max_fee_rate`] or doesn't add up with the amount.
+    FeeAnomaly,
+    /// A matching row whose amount is held back from settlement.
+    OnHold,
+    /// A transfer or adjustment row, which the ledger doesn't track.
+    Untracked,
+}
+
+#[derive(Debug, Default, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct ReconCounts {
+    pub matched: usize,
+    pub missing_in_ledger: usize,
+    pub missing_in_recon: usize,
+    pub amount_mismatch: usize,
+    pub fee_anomaly: usize,
+    pub on_hold: usize,
+    pub untracked: usize,
+}
+
+impl ReconCounts {
+    fn add(&mut self, status: ReconStatus) {
+        match status {
+            ReconStatus::Matched => self.matched += 1,
+            ReconStatus::MissingInLedger => self.missing_in_ledger += 1,
+            ReconStatus::MissingInRecon => self.missing_in_recon += 1,
+            ReconStatus::AmountMismatch => self.amount_mismatch += 1,
+            ReconStatus::FeeAnomaly => self.fee_anomaly += 1,
+            ReconStatus::OnHold => self.on_hold += 1,
+            ReconStatus::Untracked => self.untracked += 1,
+        }
+    }
+}
+
+/// A recon row, a ledger entry or both, along with how they compare.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ReconItem {
+    pub status: ReconStatus,
+    #[serde(rename = "type")]
+    pub type_: Option<SettlementType>,
+    pub entity_id: Option<SettlementReconEntityId>,
+    pub settlement_id: Option<SettlementId>,
+    pub payment_id: Option<PaymentId>,
+    pub order_id: Option<OrderId>,
+    pub order_receipt: Option<String>,
+    pub reference: Option<String>,
+    /// The amount of the row.
+    pub amount: Option<u64>,
+    /// The amount of the ledger entry.
+    pub ledger_amount: Option<u64>,
+    pub fee: Option<u64>,
+    pub tax: Option<u64>,
+    pub expected_fee: Option<u64>,
+}
+
+/// The rows of a settlement added up, rows not settled yet are grouped
+/// under no settlement.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SettlementTotals {
+    pub settlement_id: Option<SettlementId>,
+    pub settlement_utr: Option<String>,
+    pub rows: usize,
+    pub credit: u64,
+    pub debit: u64,
+    pub fee: u64,
+    pub tax: u64,
+    /// Credits less debits, the amount the rows should settle.
+    pub net: i64,
+    /// The amount of rows on hold.
+    pub on_hold: u64,
+    /// The amount of the [`Settlement`], if it was given.
+    pub settled_amount: Option<u64>,
+    /// Whether `net` matches the amount of the [`Settlement`].
+    pub settled_amount_matches: Option<bool>,
+    pub counts: ReconCounts,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct ReconReport {
+    pub items: Vec<ReconItem>,
+    pub settlements: Vec<SettlementTotals>,
+    pub counts: ReconCounts,
+}
+
+impl ReconReport {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Every item but the matched ones.
+    pub fn exceptions(&self) -> impl Iterator<Item = &ReconItem> {
+        self.items
+            .iter()
+            .filter(|item| item.status != ReconStatus::Matched)
+    }
+}
+
+/// Ledger entries by each of the keys a row is matched with.
+struct LedgerIndex<'a> {
+    entries: &'a [LedgerEntry],
+    used: Vec<bool>,
+    payments: HashMap<&'a str, Vec<usize>>,
+    refunds: HashMap<&'a str, Vec<usize>>,
+    orders: HashMap<&'a str, Vec<usize>>,
+    receipts: HashMap<&'a str, Vec<usize>>,
+    refunds_by_payment: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> LedgerIndex<'a> {
+    fn new(entries: &'a [LedgerEntry]) -> Self {
+        let mut index = Self {
+            entries,
+            used: Vec::new(),
+            payments: HashMap::new(),
+            refunds: HashMap::new(),
+            orders: HashMap::new(),
+            receipts: HashMap::new(),
+            refunds_by_payment: HashMap::new(),
+        };
+        index.used.resize(entries.len(), false);
+
+        for (i, entry) in entries.iter().enumerate() {
+            let payment_id = entry.payment_id.as_ref().map(|id| id.as_str());
+
+            match entry.kind {
+                LedgerEntryKind::Payment => {
+                    if let Some(payment_id) = payment_id {
+                        index.payments.entry(payment_id).or_default().push(i);
+                    }
+                    if let Some(order_id) = &entry.order_id {
+                        index
+                            .orders
+                            .entry(order_id.as_str())
+                            .or_default()
+                            .push(i);
+                    }
+                    if let Some(receipt) = &entry.order_receipt {
+                        index.receipts.entry(receipt).or_default().push(i);
+                    }
+                }
+                LedgerEntryKind::Refund => {
+                    if let Some(refund_id) = &entry.refund_id {
+                        index
+                            .refunds
+                            .entry(refund_id.as_str())
+                            .or_default()
+                            .push(i);
+                    }
+                    if let Some(payment_id) = payment_id {
+                        index
+                            .refunds_by_payment
+                            .entry(payment_id)
+                            .or_default()
+                            .push(i);
+                    }
+                }
+            }
+        }
+
+        index
+    }
+
+    // takes the first entry of `key` not matched to a row yet
+    fn take(
+        used: &mut [bool],
+        map: &HashMap<&'a str, Vec<usize>>,
+        key: Option<&str>,
+    ) -> Option<usize> {
+        let i = *map.get(key?)?.iter().find(|&&i| !used[i])?;
+        used[i] = true;
+        Some(i)
+    }
+
+    // takes the first entry of `key` with the amount and currency of `row`,
+    // or else the first entry of `key`, which is then an amount mismatch;
+    // an order may have been paid or a payment refunded more than once
+    fn take_same_amount(
+        used: &mut [bool],
+        entries: &[LedgerEntry],
+        map: &HashMap<&'a str, Vec<usize>>,
+        key: Option<&str>,
+        row: &SettlementRecon,
+    ) -> Option<usize> {
+        let candidates = map.get(key?)?;
+        let i = candidates
+            .iter()
+            .find(|&&i| {
+                !used[i]
+                    && entries[i].amount == row.amount
+                    && entries[i].currency == row.currency
+            })
+            .or_else(|| candidates.iter().find(|&&i| !used[i]))?;
+        used[*i] = true;
+        Some(*i)
+    }
+
+    fn take_for(&mut self, row: &SettlementRecon) -> Option<usize> {
+        let used = &mut self.used;
+
+        match (&row.type_, &row.entity_id) {
+            (SettlementType::Payment, entity_id) => {
+                let payment_id = match entity_id {
+                    SettlementReconEntityId::Payment(id) => Some(id),
+                    _ => row.payment_id.as_ref(),
+                };
+
+                Self::take(
+                    used,
+                    &self.payments,
+                    payment_id.map(|id| id.as_str()),
+                )
+                .or_else(|| {
+                    Self::take_same_amount(
+                        used,
+                        self.entries,
+                        &self.orders,
+                        row.order_id.as_ref().map(|id| id.as_str()),
+                        row,
+                    )
+                })
+                .or_else(|| {
+                    Self::take_same_amount(
+                        used,
+                        self.entries,
+                        &self.receipts,
+                        row.order_receipt.as_deref(),
+                        row,
+                    )
+                })
+            }
+            (SettlementType::Refund, entity_id) => {
+                let refund_id = match entity_id {
+                    SettlementReconEntityId::Refund(id) => Some(id.as_str()),
+                    _ => None,
+                };
+
+                Self::take(used, &self.refunds, refund_id).or_else(|| {
+                    Self::take_same_amount(
+                        used,
+                        self.entries,
+                        &self.refunds_by_payment,
+                        row.payment_id.as_ref().map(|id| id.as_str()),
+                        row,
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Matches recon rows with ledger entries, see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reconciler {
+    fee_tolerance: u64,
+    max_fee_rate: Option<u32>,
+}
+
+impl Default for Reconciler {
+    fn default() -> Self {
+        Self {
+            fee_tolerance: 0,
+            max_fee_rate: Some(300),
+        }
+    }
+}
+
+impl Reconciler {
+    /// Fees must equal the expected fee of the ledger entry exactly, or be
+    /// at most 3% of the amount if it has none.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How far, in the smallest currency unit, a fee may be from the
+    /// expected fee.
+    pub fn fee_tolerance(mut self, fee_tolerance: u64) -> Self {
+        self.fee_tolerance = fee_tolerance;
+        self
+    }
+
+    /// The largest fee, in basis points of the amount, allowed for entries
+    /// without an expected fee, `None` to allow any fee.
+    pub fn max_fee_rate(mut self, max_fee_rate: Option<u32>) -> Self {
+        self.max_fee_rate = max_fee_rate;
+        self
+    }
+
+    fn fee_is_anomalous(
+        &self,
+        row: &SettlementRecon,
+        entry: &LedgerEntry,
+    ) -> bool {
+        // the tax is a part of the fee, and a payment is credited net of it
+        if row.tax > row.fee
+            || (row.type_ == SettlementType::Payment
+                && row.credit + row.fee != row.amount)
+        {
+            return true;
+        }
+
+        match (entry.expected_fee, self.max_fee_rate) {
+            (Some(expected_fee), _) => {
+                row.fee.abs_diff(expected_fee) > self.fee_tolerance
+            }
+            (None, Some(max_fee_rate)) => {
+                u128::from(row.fee) * 10_000
+                    > u128::from(row.amount) * u128::from(max_fee_rate)
+            }
+            (None, None) => false,
+        }
+    }
+
+    fn status(
+        &self,
+        row: &SettlementRecon,
+        entry: &LedgerEntry,
+    ) -> ReconStatus {
+        if row.amount != entry.amount || row.currency != entry.currency {
+            ReconStatus::AmountMismatch
+        } else if self.fee_is_anomalous(row, entry) {
+            ReconStatus::FeeAnomaly
+        } else if row.on_hold {
+            ReconStatus::OnHold
+        } else {
+            ReconStatus::Matched
+        }
+    }
+
+    /// Matches every row with a ledger entry and adds up the rows of every
+    /// settlement, comparing them with `settlements` when given.
+    pub fn reconcile(
+        &self,
+        rows: &[SettlementRecon],
+        ledger: &[LedgerEntry],
+        settlements: &[Settlement],
+    ) -> ReconReport {
+        let mut index = LedgerIndex::new(ledger);
+        let mut report = ReconReport {
+            items: Vec::new(),
+            settlements: Vec::new(),
+            counts: ReconCounts::default(),
+        };
+        let mut totals_by_settlement: HashMap<Option<&str>, usize> =
+            HashMap::new();
+
+        for row in rows {
+            let entry = index.take_for(row).map(|i| &ledger[i]);
+            let status = match (&row.type_, entry) {
+                (_, Some(entry)) => self.status(row, entry),
+                (SettlementType::Payment | SettlementType::Refund, None) => {
+                    ReconStatus::MissingInLedger
+                }
+                _ => ReconStatus::Untracked,
+            };
+
+            let key = row.settlement_id.as_ref().map(|id| id.as_str());
+            let i = *totals_by_settlement.entry(key).or_insert_with(|| {
+                report.settlements.push(SettlementTotals {
+                    settlement_id: row.settlement_id.clone(),
+                    settlement_utr: row.settlement_utr.clone(),
+                    rows: 0,
+                    credit: 0,
+                    debit: 0,
+                    fee: 0,
+                    tax: 0,
+                    net: 0,
+                    on_hold: 0,
+                    settled_amount: None,
+                    settled_amount_matches: None,
+                    counts: ReconCounts::default(),
+                });
+                report.settlements.len() - 1
+            });
+
+            let totals = &mut report.settlements[i];
+            totals.rows += 1;
+            totals.credit += row.credit;
+            totals.debit += row.debit;
+            totals.fee += row.fee;
+            totals.tax += row.tax;
+            totals.net += row.credit as i64 - row.debit as i64;
+            if row.on_hold {
+                totals.on_hold += row.amount;
+            }
+            totals.counts.add(status);
+            report.counts.add(status);
+
+            report.items.push(ReconItem {
+                status,
+                type_: Some(row.type_.clone()),
+                entity_id: Some(row.entity_id.clone()),
+                settlement_id: row.settlement_id.clone(),
+                payment_id: row.payment_id.clone().or_else(|| {
+                    match &row.entity_id {
+                        SettlementReconEntityId::Payment(id) => {
+                            Some(id.clone())
+                        }
+                        _ => None,
+                    }
+                }),
+                order_id: row.order_id.clone(),
+                order_receipt: row.order_receipt.clone(),
+                reference: entry.and_then(|entry| entry.reference.clone()),
+                amount: Some(row.amount),
+                ledger_amount: entry.map(|entry| entry.amount),
+                fee: Some(row.fee),
+                tax: Some(row.tax),
+                expected_fee: entry.and_then(|entry| entry.expected_fee),
+            });
+        }
+
+        for (entry, _) in
+            ledger.iter().zip(&index.used).filter(|(_, &used)| !used)
+        {
+            report.counts.add(ReconStatus::MissingInRecon);
+            report.items.push(ReconItem {
+                status: ReconStatus::MissingInRecon,
+                type_: Some(match entry.kind {
+                    LedgerEntryKind::Payment => SettlementType::Payment,
+                    LedgerEntryKind::Refund => SettlementType::Refund,
+                }),
+                entity_id: match entry.kind {
+                    LedgerEntryKind::Payment => entry
+                        .payment_id
+                        .clone()
+                        .map(SettlementReconEntityId::Payment),
+                    LedgerEntryKind::Refund => entry
+                        .refund_id
+                        .clone()
+                        .map(SettlementReconEntityId::Refund),
+                },
+                settlement_id: None,
+                payment_id: entry.payment_id.clone(),
+                order_id: entry.order_id.clone(),
+                order_receipt: entry.order_receipt.clone(),
+                reference: entry.reference.clone(),
+                amount: None,
+                ledger_amount: Some(entry.amount),
+                fee: None,
+                tax: None,
+                expected_fee: entry.expected_fee,
+            });
+        }
+
+        for totals in &mut report.settlements {
+            let settlement = totals.settlement_id.as_ref().and_then(|id| {
+                settlements.iter().find(|settlement| &settlement.id == id)
+            });
+
+            if let Some(settlement) = settlement {
+                totals.settled_amount = Some(settlement.amount);
+                totals.settled_amount_matches =
+                    Some(totals.net == settlement.amount as i64);
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    // a settled payment row of setl_A, overridden by `fields`
+    fn row(fields: Value) -> SettlementRecon {
+        let mut row = json!({
+            "entity_id": "pay_1", "type": "payment",
+            "debit": 0, "credit": 9800, "amount": 10000, "currency": "INR",
+            "fee": 200, "tax": 30, "on_hold": false, "settled": true,
+            "created_at": 1567692556, "settled_at": 1568176960,
+            "settlement_id": "setl_A", "description": null, "notes": [],
+            "payment_id": null, "settlement_utr": "utr_A",
+            "order_id": null, "order_receipt": null, "method": "card",
+            "card_network": null, "card_issuer": null, "card_type": null,
+            "dispute_id": null
+        });
+        row.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(row).unwrap()
+    }
+
+    fn entries(entries: Value) -> Vec<LedgerEntry> {
+        serde_json::from_value(entries).unwrap()
+    }
+
+    fn item<'a>(report: &'a ReconReport, entity_id: &str) -> &'a ReconItem {
+        report
+            .items
+            .iter()
+            .find(|item| {
+                serde_json::to_value(&item.entity_id).unwrap() == entity_id
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn reports_every_status() {
+        let rows = [
+            row(json!({ "entity_id": "pay_matched" })),
+            row(json!({
+                "entity_id": "pay_short", "amount": 5000, "credit": 4900,
+                "fee": 100, "tax": 15
+            })),
+            row(json!({
+                "entity_id": "pay_expensive", "credit": 9500, "fee": 500,
+                "tax": 76
+            })),
+            row(json!({
+                "entity_id": "pay_held", "on_hold": true, "settled": false,
+                "settled_at": null, "settlement_id": null,
+                "settlement_utr": null
+            })),
+            row(json!({ "entity_id": "pay_unknown" })),
+            row(json!({
+                "entity_id": "trf_1", "type": "transfer", "credit": 0,
+                "debit": 1000, "amount": 1000, "fee": 0, "tax": 0
+            })),
+        ];
+        let ledger = entries(json!([
+            { "kind": "payment", "payment_id": "pay_matched", "amount": 10000,
+              "currency": "INR" },
+            { "kind": "payment", "payment_id": "pay_short", "amount": 6000,
+              "currency": "INR" },
+            { "kind": "payment", "payment_id": "pay_expensive",
+              "amount": 10000, "currency": "INR" },
+            { "kind": "payment", "payment_id": "pay_held", "amount": 10000,
+              "currency": "INR" },
+            { "kind": "payment", "payment_id": "pay_lost", "amount": 10000,
+              "currency": "INR", "reference": "INV-7" },
+        ]));
+
+        let report = Reconciler::new().reconcile(&rows, &ledger, &[]);
+
+        assert_eq!(item(&report, "pay_matched").status, ReconStatus::Matched);
+        let short = item(&report, "pay_short");
+        assert_eq!(short.status, ReconStatus::AmountMismatch);
+        assert_eq!(
+            (short.amount, short.ledger_amount),
+            (Some(5000), Some(6000))
+        );
+        assert_eq!(
+            item(&report, "pay_expensive").status,
+            ReconStatus::FeeAnomaly
+        );
+        assert_eq!(item(&report, "pay_held").status, ReconStatus::OnHold);
+        assert_eq!(
+            item(&report, "pay_unknown").status,
+            ReconStatus::MissingInLedger
+        );
+        assert_eq!(item(&report, "trf_1").status, ReconStatus::Untracked);
+        let lost = item(&report, "pay_lost");
+        assert_eq!(lost.status, ReconStatus::MissingInRecon);
+        assert_eq!(lost.reference.as_deref(), Some("INV-7"));
+
+        assert_eq!(
+            report.counts,
+            ReconCounts {
+                matched: 1,
+                missing_in_ledger: 1,
+                missing_in_recon: 1,
+                amount_mismatch: 1,
+                fee_anomaly: 1,
+                on_hold: 1,
+                untracked: 1,
+            }
+        );
+        assert_eq!(report.exceptions().count(), 6);
+    }
+
+    #[test]
+    fn matches_refunds_of_a_payment_by_amount() {
+        let rows = [
+            row(json!({
+                "entity_id": "rfnd_1", "type": "refund", "payment_id": "pay_1",
+                "credit": 0, "debit": 4000, "amount": 4000, "fee": 0, "tax": 0
+            })),
+            row(json!({
+                "entity_id": "rfnd_2", "type": "refund", "payment_id": "pay_1",
+                "credit": 0, "debit": 1000, "amount": 1000, "fee": 0, "tax": 0
+            })),
+            row(json!({
+                "entity_id": "rfnd_3", "type": "refund", "payment_id": "pay_1",
+                "credit": 0, "debit": 700, "amount": 700, "fee": 0, "tax": 0
+            })),
+        ];
+        let ledger = entries(json!([
+            { "kind": "refund", "payment_id": "pay_1", "amount": 1000,
+              "currency": "INR", "reference": "RF-1" },
+            { "kind": "refund", "payment_id": "pay_1", "amount": 4000,
+              "currency": "INR", "reference": "RF-2" },
+            { "kind": "refund", "payment_id": "pay_1", "amount": 500,
+              "currency": "INR", "reference": "RF-3" },
+        ]));
+
+        let report = Reconciler::new().reconcile(&rows, &ledger, &[]);
+
+        let first = item(&report, "rfnd_1");
+        assert_eq!(first.status, ReconStatus::Matched);
+        assert_eq!(first.reference.as_deref(), Some("RF-2"));
+        let second = item(&report, "rfnd_2");
+        assert_eq!(second.status, ReconStatus::Matched);
+        assert_eq!(second.reference.as_deref(), Some("RF-1"));
+        // no refund of the same amount is left, the remaining one is taken
+        let third = item(&report, "rfnd_3");
+        assert_eq!(third.status, ReconStatus::AmountMismatch);
+        assert_eq!(third.reference.as_deref(), Some("RF-3"));
+    }
+
+    #[test]
+    fn falls_back_to_order_id_then_receipt() {
+        let rows = [
+            row(json!({ "entity_id": "pay_1", "order_id": "order_1" })),
+            row(json!({
+                "entity_id": "pay_2", "order_id": "order_2",
+                "amount": 3000, "credit": 2940, "fee": 60, "tax": 9
+            })),
+            row(json!({ "entity_id": "pay_3", "order_receipt": "R-3" })),
+        ];
+        let ledger = entries(json!([
+            { "kind": "payment", "order_id": "order_1", "amount": 10000,
+              "currency": "INR", "reference": "O1" },
+            { "kind": "payment", "order_id": "order_2", "amount": 2500,
+              "currency": "INR", "reference": "O2-A" },
+            { "kind": "payment", "order_id": "order_2", "amount": 2600,
+              "currency": "INR", "reference": "O2-B" },
+            { "kind": "payment", "order_receipt": "R-3", "amount": 10000,
+              "currency": "INR", "reference": "R3" },
+        ]));
+
+        let report = Reconciler::new().reconcile(&rows, &ledger, &[]);
+
+        assert_eq!(item(&report, "pay_1").reference.as_deref(), Some("O1"));
+        assert_eq!(item(&report, "pay_1").status, ReconStatus::Matched);
+        // neither entry of order_2 has the amount, the first unused one is
+        // taken and the other is left missing in the recon
+        let mismatch = item(&report, "pay_2");
+        assert_eq!(mismatch.status, ReconStatus::AmountMismatch);
+        assert_eq!(mismatch.reference.as_deref(), Some("O2-A"));
+        assert!(report.items.iter().any(|item| {
+            item.status == ReconStatus::MissingInRecon
+                && item.reference.as_deref() == Some("O2-B")
+        }));
+        assert_eq!(item(&report, "pay_3").reference.as_deref(), Some("R3"));
+        assert_eq!(item(&report, "pay_3").status, ReconStatus::Matched);
+    }
+
+    #[test]
+    fn checks_fees_against_expected_fee_and_rate() {
+        let rows = [
+            row(json!({ "entity_id": "pay_1", "credit": 9750, "fee": 250 })),
+            row(json!({ "entity_id": "pay_2", "credit": 9700 })),
+            row(json!({ "entity_id": "pay_3", "fee": 200, "tax": 300 })),
+        ];
+        let ledger = entries(json!([
+            { "kind": "payment", "payment_id": "pay_1", "amount": 10000,
+              "currency": "INR", "expected_fee": 200 },
+            { "kind": "payment", "payment_id": "pay_2", "amount": 10000,
+              "currency": "INR" },
+            { "kind": "payment", "payment_id": "pay_3", "amount": 10000,
+              "currency": "INR" },
+        ]));
+
+        let strict = Reconciler::new().reconcile(&rows, &ledger, &[]);
+        // off by 50 from the expected fee
+        assert_eq!(item(&strict, "pay_1").status, ReconStatus::FeeAnomaly);
+        // credit and fee don't add up to the amount
+        assert_eq!(item(&strict, "pay_2").status, ReconStatus::FeeAnomaly);
+        // the tax exceeds the fee it is part of
+        assert_eq!(item(&strict, "pay_3").status, ReconStatus::FeeAnomaly);
+
+        let lenient = Reconciler::new().fee_tolerance(50).reconcile(
+            &rows[..1],
+            &ledger,
+            &[],
+        );
+        assert_eq!(item(&lenient, "pay_1").status, ReconStatus::Matched);
+
+        // 10% of the amount, above the default rate of 3%
+        let rows = [row(json!({ "credit": 9000, "fee": 1000, "tax": 0 }))];
+        let ledger = entries(json!([
+            { "kind": "payment", "payment_id": "pay_1", "amount": 10000,
+              "currency": "INR" },
+        ]));
+        let capped = Reconciler::new().reconcile(&rows, &ledger, &[]);
+        assert_eq!(capped.items[0].status, ReconStatus::FeeAnomaly);
+        let uncapped =
+            Reconciler::new()
+                .max_fee_rate(None)
+                .reconcile(&rows, &ledger, &[]);
+        assert_eq!(uncapped.items[0].status, ReconStatus::Matched);
+    }
+
+    #[test]
+    fn adds_up_settlements_and_compares_them() {
+        let rows = [
+            row(json!({ "entity_id": "pay_1" })),
+            row(json!({
+                "entity_id": "rfnd_1", "type": "refund", "payment_id": "pay_1",
+                "credit": 0, "debit": 1000, "amount": 1000, "fee": 0, "tax": 0
+            })),
+            row(json!({
+                "entity_id": "pay_2", "settlement_id": "setl_B",
+                "settlement_utr": "utr_B"
+            })),
+            row(json!({
+                "entity_id": "pay_3", "on_hold": true, "settled": false,
+                "settled_at": null, "settlement_id": null,
+                "settlement_utr": null
+            })),
+        ];
+        let settlements: Vec<Settlement> = serde_json::from_value(json!([
+            { "entity": "settlement", "id": "setl_A", "amount": 8800,
+              "status": "processed", "fees": 200, "tax": 30, "utr": "utr_A",
+              "created_at": 1568176960 },
+            { "entity": "settlement", "id": "setl_B", "amount": 9900,
+              "status": "processed", "fees": 200, "tax": 30, "utr": "utr_B",
+              "created_at": 1568176960 },
+        ]))
+        .unwrap();
+
+        let report = Reconciler::new().reconcile(&rows, &[], &settlements);
+
+        let [a, b, unsettled] = &report.settlements[..] else {
+            panic!("{:?}", report.settlements);
+        };
+        assert_eq!(a.settlement_id.as_ref().unwrap().as_str(), "setl_A");
+        assert_eq!((a.rows, a.credit, a.debit, a.net), (2, 9800, 1000, 8800));
+        assert_eq!((a.fee, a.tax), (200, 30));
+        assert_eq!(a.settled_amount_matches, Some(true));
+        assert_eq!(a.counts.missing_in_ledger, 2);
+
+        assert_eq!(b.settled_amount, Some(9900));
+        assert_eq!(b.net, 9800);
+        assert_eq!(b.settled_amount_matches, Some(false));
+
+        assert_eq!(unsettled.settlement_id, None);
+        assert_eq!(unsettled.on_hold, 10000);
+        assert_eq!(unsettled.settled_amount_matches, None);
+    }
+}
@@ -0,0 +1,330 @@
+//! Recurring payments charged against a mandate registered by the customer,
+//! an emandate, a card mandate or a UPI Autopay mandate.
+//!
+//! The customer first authorizes a mandate, either by paying a registration
+//! order ([`Order::create_registration`]) or through an authorization link
+//! ([`Invoice::create_authorization_link`]). The payment creates a
+//! [`Token`], every following payment is charged with
+//! [`Payment::create_recurring`] against an order of its own.
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String};
+
+use chrono::{serde::ts_seconds_option, DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RequestParams,
+    common::{Currency, Object},
+    error::{InternalApiResult, RazorpayResult},
+    invoice::Invoice,
+    order::Order,
+    payment::Payment,
+    token::Token,
+    CustomerId, OrderId, PaymentId, Razorpay, TokenId,
+};
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MandateMethod {
+    Emandate,
+    Card,
+    /// UPI Autopay.
+    Upi,
+    Nach,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MandateAuthType {
+    NetBanking,
+    DebitCard,
+    Aadhaar,
+    /// A signed paper NACH form.
+    Physical,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MandateFrequency {
+    Daily,
+    Weekly,
+    Fortnightly,
+    Bimonthly,
+    Monthly,
+    Quarterly,
+    HalfYearly,
+    Yearly,
+    AsPresented,
+}
+
+/// When UPI Autopay debits are allowed relative to `recurring_value`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpiRecurringType {
+    Before,
+    On,
+    After,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MandateBankAccountType {
+    Savings,
+    Current,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct MandateBankAccount<'a> {
+    pub beneficiary_name: &'a str,
+    pub account_number: &'a str,
+    pub account_type: MandateBankAccountType,
+    pub ifsc_code: &'a str,
+}
+
+/// The mandate the customer authorizes, only `max_amount`, `expire_at` and
+/// `frequency` apply to every method.
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateMandate<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<u64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub expire_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<MandateFrequency>,
+    /// Emandate and NACH only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_type: Option<MandateAuthType>,
+    /// Emandate and NACH only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank_account: Option<MandateBankAccount<'a>>,
+    /// Charged along with the registration, emandate only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_payment_amount: Option<u64>,
+    /// UPI Autopay only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurring_type: Option<UpiRecurringType>,
+    /// The day of the week or month debits are made on, UPI Autopay only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurring_value: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateRegistrationOrder<'a> {
+    /// `0` for emandate and NACH, the first charge for card and UPI
+    /// Autopay mandates.
+    pub amount: u64,
+    pub currency: Currency,
+    pub method: MandateMethod,
+    pub customer_id: &'a CustomerId,
+    pub token: CreateMandate<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_capture: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthorizationLinkType {
+    #[default]
+    Link,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct AuthorizationLinkCustomer<'a> {
+    pub name: &'a str,
+    pub email: &'a str,
+    pub contact: &'a str,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SubscriptionRegistration<'a> {
+    pub method: MandateMethod,
+    #[serde(flatten)]
+    pub mandate: CreateMandate<'a>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateAuthorizationLink<'a> {
+    #[serde(rename = "type")]
+    pub type_: AuthorizationLinkType,
+    pub customer: AuthorizationLinkCustomer<'a>,
+    pub amount: u64,
+    pub currency: Currency,
+    pub description: &'a str,
+    pub subscription_registration: SubscriptionRegistration<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sms_notify: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_notify: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub expire_by: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+}
+
+/// A payment charged against a mandate, the order must be created for the
+/// same amount beforehand.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateRecurringPayment<'a> {
+    pub email: &'a str,
+    pub contact: &'a str,
+    pub amount: u64,
+    pub currency: Currency,
+    pub order_id: &'a OrderId,
+    pub customer_id: &'a CustomerId,
+    pub token: &'a TokenId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct RecurringPaymentRequest<'a> {
+    #[serde(flatten)]
+    params: CreateRecurringPayment<'a>,
+    recurring: &'static str,
+}
+
+/// The result of [`Payment::create_recurring`], UPI Autopay payments are
+/// processed asynchronously and only reported through webhooks.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct RecurringPayment {
+    pub razorpay_payment_id: PaymentId,
+    pub razorpay_order_id: Option<OrderId>,
+    pub razorpay_signature: Option<String>,
+}
+
+impl Order {
+    /// Creates the order the customer pays to authorize a mandate.
+    pub async fn create_registration(
+        razorpay: &Razorpay,
+        params: CreateRegistrationOrder<'_>,
+    ) -> RazorpayResult<Order> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/orders".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(order) => Ok(order),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+impl Invoice {
+    /// Creates a link the customer opens to authorize a mandate, Razorpay
+    /// sends it by SMS and email unless disabled.
+    pub async fn create_authorization_link(
+        razorpay: &Razorpay,
+        params: CreateAuthorizationLink<'_>,
+    ) -> RazorpayResult<Invoice> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/subscription_registration/auth_links".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(invoice) => Ok(invoice),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+impl Payment {
+    pub async fn create_recurring(
+        razorpay: &Razorpay,
+        params: CreateRecurringPayment<'_>,
+    ) -> RazorpayResult<RecurringPayment> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payments/create/recurring".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(RecurringPaymentRequest {
+                    params,
+                    recurring: "1",
+                }),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment) => Ok(payment),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+impl Token {
+    /// Fetches the token created by the registration payment of a mandate.
+    pub async fn fetch_for_payment(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+    ) -> RazorpayResult<Option<Token>> {
+        let payment = Payment::fetch(razorpay, payment_id, &[]).await?;
+
+        match (payment.customer_id, payment.token_id) {
+            (Some(customer_id), Some(token_id)) => {
+                Token::fetch(razorpay, &customer_id, &token_id)
+                    .await
+                    .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Cancels a mandate, no further payments can be charged against it.
+    pub async fn cancel(
+        razorpay: &Razorpay,
+        customer_id: &CustomerId,
+        token_id: &TokenId,
+    ) -> RazorpayResult<Token> {
+        let res = razorpay
+            .api
+            .put(RequestParams {
+                url: format!(
+                    "/customers/{}/tokens/{}/cancel",
+                    customer_id, token_id
+                ),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(token) => Ok(token),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
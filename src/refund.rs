@@ -2,6 +2,7 @@
 use alloc::{borrow::ToOwned, format, string::String};
 
 use chrono::{serde::ts_seconds, DateTime, Utc};
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -10,6 +11,7 @@ use crate::{
     common::{Collection, Currency, Filter, Object},
     error::{InternalApiResult, RazorpayResult},
     ids::RefundId,
+    pagination::paginate,
     util::deserialize_notes,
     BatchId, PaymentId, Razorpay,
 };
@@ -37,7 +39,9 @@ pub struct Refund {
     pub amount: u64,
     pub currency: Currency,
     pub payment_id: PaymentId,
-    pub speed: RefundSpeed,
+    /// Only sent by older API versions, see `speed_requested` and
+    /// `speed_processed`.
+    pub speed: Option<RefundSpeed>,
     #[serde(with = "ts_seconds")]
     pub created_at: DateTime<Utc>,
     pub batch_id: Option<BatchId>,
@@ -70,6 +74,8 @@ pub struct CreateRefund<'a> {
     pub notes: Option<Object>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub receipt: Option<&'a str>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
 }
 
 impl Refund {
@@ -85,6 +91,7 @@ impl Refund {
             .get(RequestParams {
                 url: "/refunds".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -95,6 +102,16 @@ impl Refund {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Refund>> + '_
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| Refund::list(razorpay, filter))
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         refund_id: &RefundId,
@@ -104,6 +121,7 @@ impl Refund {
             .get(RequestParams {
                 url: format!("/refunds/{}", refund_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -124,6 +142,7 @@ impl Refund {
             .patch(RequestParams {
                 url: format!("/refunds/{}", refund_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({
                     "notes": notes
                 })),
@@ -0,0 +1,276 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, string::String};
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+#[cfg(feature = "std")]
+use reqwest::{Method, Request};
+#[cfg(feature = "std")]
+use ring::rand::{SecureRandom, SystemRandom};
+
+#[cfg(feature = "std")]
+use crate::error::{RazorpayError, RazorpayErrorKind};
+
+pub(crate) const IDEMPOTENCY_KEY_HEADER: &str = "X-Idempotency-Key";
+pub(crate) const PAYOUT_IDEMPOTENCY_HEADER: &str = "X-Payout-Idempotency";
+
+/// An idempotency key along with the header it is sent in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum IdempotencyKey {
+    Standard(String),
+    /// The payout APIs of RazorpayX expect their own header.
+    Payout(String),
+}
+
+impl IdempotencyKey {
+    pub(crate) fn header(&self) -> &'static str {
+        match self {
+            IdempotencyKey::Standard(_) => IDEMPOTENCY_KEY_HEADER,
+            IdempotencyKey::Payout(_) => PAYOUT_IDEMPOTENCY_HEADER,
+        }
+    }
+
+    pub(crate) fn value(&self) -> &str {
+        match self {
+            IdempotencyKey::Standard(key) | IdempotencyKey::Payout(key) => key,
+        }
+    }
+}
+
+impl From<&str> for IdempotencyKey {
+    fn from(key: &str) -> Self {
+        IdempotencyKey::Standard(key.to_owned())
+    }
+}
+
+/// Controls how failed requests are retried by the client.
+///
+/// Requests are retried on connection errors, `5xx` and `429` responses.
+/// `GET`, `PUT` and `DELETE` requests are always considered safe to retry,
+/// `POST` and `PATCH` requests only when they carry an idempotency key
+/// (`X-Idempotency-Key`, or `X-Payout-Idempotency` for payouts).
+///
+/// Waiting between attempts needs the `std` feature, without it every request
+/// is sent once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every following retry.
+    pub base_delay: Duration,
+    /// Upper bound for the delay between two attempts, a `Retry-After`
+    /// larger than this stops retrying.
+    pub max_delay: Duration,
+    /// Randomize up to half of every delay.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn can_retry(&self, request: &Request) -> bool {
+        self.max_attempts > 1
+            && match *request.method() {
+                Method::GET
+                | Method::HEAD
+                | Method::OPTIONS
+                | Method::PUT
+                | Method::DELETE => true,
+                _ => {
+                    request.headers().contains_key(IDEMPOTENCY_KEY_HEADER)
+                        || request
+                            .headers()
+                            .contains_key(PAYOUT_IDEMPOTENCY_HEADER)
+                }
+            }
+    }
+
+    /// Returns how long to wait before sending the request again after
+    /// `attempt` failed with `error`, or `None` if it should not be retried.
+    #[cfg(feature = "std")]
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        error: &RazorpayError,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retryable = match error.status() {
+            Some(status) => status.is_server_error() || status.as_u16() == 429,
+            None => error.kind() == RazorpayErrorKind::Network,
+        };
+
+        if !retryable {
+            return None;
+        }
+
+        if let Some(retry_after) = error.retry_after() {
+            return (retry_after <= self.max_delay).then_some(retry_after);
+        }
+
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+
+        if !self.jitter {
+            return Some(delay);
+        }
+
+        let half = delay / 2;
+        Some(half + random_up_to(delay - half))
+    }
+}
+
+#[cfg(feature = "std")]
+fn random_up_to(max: Duration) -> Duration {
+    let mut bytes = [0; 8];
+    if SystemRandom::new().fill(&mut bytes).is_err() {
+        return max;
+    }
+
+    let nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+    Duration::from_nanos(u64::from_le_bytes(bytes) % nanos.saturating_add(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{header::HeaderValue, StatusCode};
+
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+            jitter: false,
+        }
+    }
+
+    fn failure(status: u16, retry_after: Option<u64>) -> RazorpayError {
+        RazorpayError::HttpError {
+            status: StatusCode::from_u16(status).unwrap(),
+            body: String::new(),
+            retry_after: retry_after.map(Duration::from_secs),
+        }
+    }
+
+    #[test]
+    fn doubles_the_delay_up_to_the_maximum() {
+        let error = failure(503, None);
+        let delays: Vec<Option<Duration>> = (1..=6)
+            .map(|attempt| policy().delay(attempt, &error))
+            .collect();
+
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_millis(500)),
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(4)),
+            ]
+        );
+
+        let endless = RetryPolicy {
+            max_attempts: u32::MAX,
+            ..policy()
+        };
+        assert_eq!(endless.delay(100, &error), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn stops_after_the_last_attempt() {
+        let error = failure(500, None);
+
+        assert!(policy().delay(9, &error).is_some());
+        assert_eq!(policy().delay(10, &error), None);
+        assert_eq!(RetryPolicy::none().delay(1, &error), None);
+    }
+
+    #[test]
+    fn retries_only_server_errors_and_rate_limits() {
+        assert!(policy().delay(1, &failure(429, None)).is_some());
+        assert!(policy().delay(1, &failure(502, None)).is_some());
+        assert_eq!(policy().delay(1, &failure(400, None)), None);
+        assert_eq!(policy().delay(1, &failure(404, None)), None);
+    }
+
+    #[test]
+    fn waits_for_retry_after_up_to_the_maximum() {
+        assert_eq!(
+            policy().delay(1, &failure(429, Some(3))),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy().delay(5, &failure(503, Some(0))),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(policy().delay(1, &failure(429, Some(5))), None);
+    }
+
+    #[test]
+    fn jitters_within_the_upper_half() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy()
+        };
+
+        for _ in 0..100 {
+            let delay = policy.delay(3, &failure(500, None)).unwrap();
+            assert!(delay >= Duration::from_secs(1), "{:?}", delay);
+            assert!(delay <= Duration::from_secs(2), "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn retries_posts_only_with_an_idempotency_key() {
+        let client = reqwest::Client::new();
+        let request = |method: Method, key: Option<&str>| {
+            let request = client.request(method, "http://localhost/v1/orders");
+            match key {
+                Some(key) => request.header(key, HeaderValue::from_static("k")),
+                None => request,
+            }
+            .build()
+            .unwrap()
+        };
+
+        assert!(policy().can_retry(&request(Method::GET, None)));
+        assert!(policy().can_retry(&request(Method::DELETE, None)));
+        assert!(!policy().can_retry(&request(Method::POST, None)));
+        assert!(!policy().can_retry(&request(Method::PATCH, None)));
+        assert!(policy()
+            .can_retry(&request(Method::POST, Some(IDEMPOTENCY_KEY_HEADER))));
+        assert!(policy().can_retry(&request(
+            Method::POST,
+            Some(PAYOUT_IDEMPOTENCY_HEADER)
+        )));
+        assert!(!RetryPolicy::none().can_retry(&request(Method::GET, None)));
+    }
+}
@@ -0,0 +1,372 @@
+//! Server-to-server payment creation, for merchants approved to collect
+//! payment details on their own checkout.
+//!
+//! A created payment isn't authorized yet, the customer still has to
+//! complete the [`PaymentNextAction`]s returned with it: open a 3DS page,
+//! enter an OTP, approve a UPI request, ...
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::{Debug, Formatter, Result as FormatterResult};
+#[cfg(feature = "std")]
+use std::fmt::{Debug, Formatter, Result as FormatterResult};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    api::RequestParams,
+    common::{Currency, Object},
+    error::{InternalApiResult, RazorpayResult},
+    payment::Payment,
+    util::mask_card_number,
+    CustomerId, OrderId, PaymentId, Razorpay,
+};
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CreatePaymentMethod {
+    Card,
+    NetBanking,
+    Wallet,
+    Emi,
+    Upi,
+}
+
+#[derive(Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreatePaymentCard<'a> {
+    pub number: &'a str,
+    pub name: &'a str,
+    pub expiry_month: &'a str,
+    pub expiry_year: &'a str,
+    pub cvv: &'a str,
+}
+
+impl Debug for CreatePaymentCard<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        f.debug_struct("CreatePaymentCard")
+            .field("number", &mask_card_number(self.number))
+            .field("name", &self.name)
+            .field("expiry_month", &self.expiry_month)
+            .field("expiry_year", &self.expiry_year)
+            .field("cvv", &"***")
+            .finish()
+    }
+}
+
+/// The device the customer pays from, required by the card networks.
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct PaymentDevice<'a> {
+    pub ip: &'a str,
+    pub referer: &'a str,
+    pub user_agent: &'a str,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreatePayment<'a> {
+    pub amount: u64,
+    pub currency: Currency,
+    pub order_id: &'a OrderId,
+    pub email: &'a str,
+    pub contact: &'a str,
+    pub method: CreatePaymentMethod,
+    #[serde(flatten)]
+    pub device: PaymentDevice<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<CreatePaymentCard<'a>>,
+    /// The bank code for netbanking payments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bank: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    /// Saves the card of the customer as a token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save: Option<bool>,
+    /// Where the customer is sent back to after authenticating.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpiFlow {
+    /// A payment request sent to the VPA of the customer.
+    Collect,
+    /// A `upi://` link opened in a UPI app of the customer.
+    Intent,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateUpiPaymentDetails<'a> {
+    pub flow: UpiFlow,
+    /// Collect flow only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vpa: Option<&'a str>,
+    /// How long the customer has to approve a collect request, in minutes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_time: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateUpiPayment<'a> {
+    pub amount: u64,
+    pub currency: Currency,
+    pub order_id: &'a OrderId,
+    pub email: &'a str,
+    pub contact: &'a str,
+    #[serde(flatten)]
+    pub device: PaymentDevice<'a>,
+    pub upi: CreateUpiPaymentDetails<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct UpiPaymentRequest<'a> {
+    #[serde(flatten)]
+    params: CreateUpiPayment<'a>,
+    method: CreatePaymentMethod,
+}
+
+/// What the customer has to do next to authorize a payment.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PaymentNextAction {
+    /// Send the customer to a page of the bank, such as a 3DS page.
+    Redirect { url: String },
+    /// Generate an OTP with [`Payment::generate_otp`].
+    OtpGenerate { url: String },
+    /// Collect the OTP sent to the customer and submit it with
+    /// [`Payment::submit_otp`].
+    OtpSubmit { url: String },
+    /// Send the OTP again with [`Payment::resend_otp`].
+    OtpResend { url: String },
+    /// Open the `upi://` link in a UPI app of the customer.
+    Intent { url: String },
+    /// Wait for the customer to approve the payment, by polling the url or
+    /// fetching the payment.
+    Poll { url: String },
+    #[serde(other)]
+    Unknown,
+}
+
+impl PaymentNextAction {
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            PaymentNextAction::Redirect { url }
+            | PaymentNextAction::OtpGenerate { url }
+            | PaymentNextAction::OtpSubmit { url }
+            | PaymentNextAction::OtpResend { url }
+            | PaymentNextAction::Intent { url }
+            | PaymentNextAction::Poll { url } => Some(url),
+            PaymentNextAction::Unknown => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct PaymentCardMetadata {
+    pub issuer: Option<String>,
+    pub network: Option<String>,
+    pub last4: Option<String>,
+    pub iin: Option<String>,
+}
+
+/// A created payment and the actions, in order of preference, that
+/// authorize it.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct CreatedPayment {
+    pub razorpay_payment_id: PaymentId,
+    #[serde(default)]
+    pub next: Vec<PaymentNextAction>,
+    pub metadata: Option<PaymentCardMetadata>,
+}
+
+impl CreatedPayment {
+    pub fn next_action(&self) -> Option<&PaymentNextAction> {
+        self.next.first()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UpiPaymentResult {
+    razorpay_payment_id: PaymentId,
+    #[serde(default)]
+    next: Vec<PaymentNextAction>,
+    link: Option<String>,
+}
+
+/// A payment authorized by the customer, verify its signature with
+/// [`verify_payment_signature`](crate::util::verify_payment_signature).
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct AuthorizedPayment {
+    pub razorpay_payment_id: PaymentId,
+    pub razorpay_order_id: Option<OrderId>,
+    pub razorpay_signature: Option<String>,
+}
+
+impl Payment {
+    pub async fn create_json(
+        razorpay: &Razorpay,
+        params: CreatePayment<'_>,
+    ) -> RazorpayResult<CreatedPayment> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payments/create/json".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment) => Ok(payment),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Creates a UPI collect or intent payment, an intent payment is
+    /// returned with a [`PaymentNextAction::Intent`] holding the
+    /// `upi://` link. Collect payments may come back without any action,
+    /// fetch the payment until the customer has approved it.
+    pub async fn create_upi(
+        razorpay: &Razorpay,
+        params: CreateUpiPayment<'_>,
+    ) -> RazorpayResult<CreatedPayment> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/payments/create/upi".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(UpiPaymentRequest {
+                    params,
+                    method: CreatePaymentMethod::Upi,
+                }),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(UpiPaymentResult {
+                razorpay_payment_id,
+                mut next,
+                link,
+            }) => {
+                if let Some(url) = link {
+                    if !next.iter().any(|action| {
+                        matches!(action, PaymentNextAction::Intent { .. })
+                    }) {
+                        next.insert(0, PaymentNextAction::Intent { url });
+                    }
+                }
+
+                Ok(CreatedPayment {
+                    razorpay_payment_id,
+                    next,
+                    metadata: None,
+                })
+            }
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Sends an OTP to the customer for a card payment created with
+    /// [`Payment::create_json`].
+    pub async fn generate_otp(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+    ) -> RazorpayResult<CreatedPayment> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payments/{}/otp_generate", payment_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment) => Ok(payment),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn submit_otp(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+        otp: &str,
+    ) -> RazorpayResult<AuthorizedPayment> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payments/{}/otp/submit", payment_id),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "otp": otp })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment) => Ok(payment),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn resend_otp(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+    ) -> RazorpayResult<CreatedPayment> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payments/{}/otp/resend", payment_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payment) => Ok(payment),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_hides_cvv() {
+        let card = CreatePaymentCard {
+            number: "4111111111111234",
+            name: "Gaurav Kumar",
+            expiry_month: "12",
+            expiry_year: "30",
+            cvv: "987",
+        };
+
+        assert_eq!(
+            format!("{:?}", card),
+            r#"CreatePaymentCard { number: "****1234", name: "Gaurav Kumar", expiry_month: "12", expiry_year: "30", cvv: "***" }"#
+        );
+    }
+}
@@ -5,6 +5,7 @@ use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -14,6 +15,7 @@ use crate::{
     common::{Collection, Currency, Filter, Object},
     error::{InternalApiResult, RazorpayResult},
     ids::{InstantSettlementId, InstantSettlementPayoutId, SettlementId},
+    pagination::paginate,
     payment::PaymentMethod,
     util::deserialize_notes,
     AdjustmentId, DisputeId, OrderId, PaymentId, Razorpay, RefundId,
@@ -101,7 +103,7 @@ pub struct InstantSettlement {
     pub ondemand_payouts: Option<Collection<InstantSettlementPayout>>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum SettlementType {
     Payment,
@@ -110,7 +112,7 @@ pub enum SettlementType {
     Adjustment,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum SettlementReconEntityId {
     Payment(PaymentId),
@@ -186,6 +188,8 @@ pub struct CreateInstantSettlement<'a> {
     pub description: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
 }
 
 impl Settlement {
@@ -201,6 +205,7 @@ impl Settlement {
             .get(RequestParams {
                 url: "/settlements".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -211,6 +216,18 @@ impl Settlement {
         }
     }
 
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Settlement>> + '_
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| {
+            Settlement::list(razorpay, filter)
+        })
+    }
+
     pub async fn fetch(
         razorpay: &Razorpay,
         settlement_id: &SettlementId,
@@ -220,6 +237,7 @@ impl Settlement {
             .get(RequestParams {
                 url: format!("/settlements/{}", settlement_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -241,6 +259,7 @@ impl SettlementRecon {
             .get(RequestParams {
                 url: "/settlements/recon/combined".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -262,6 +281,7 @@ impl InstantSettlement {
             .post(RequestParams {
                 url: "/settlements/ondemand".to_owned(),
                 version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
                 data: Some(params),
             })
             .await?;
@@ -281,6 +301,7 @@ impl InstantSettlement {
             .get(RequestParams {
                 url: "/settlements/ondemand".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: expand_payout.then_some(json!({
                     "expand[]": "ondemand_payouts",
                 })),
@@ -303,6 +324,7 @@ impl InstantSettlement {
             .get(RequestParams {
                 url: format!("/settlements/ondemand/{}", instant_settlement_id),
                 version: None,
+                idempotency_key: None,
                 data: expand_payout.then_some(json!({
                     "expand[]": "ondemand_payouts",
                 })),
@@ -2,6 +2,7 @@
 use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 
 use chrono::{serde::ts_seconds_option, DateTime, Utc};
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -11,6 +12,7 @@ use crate::{
     common::{Collection, Currency, Filter, Object},
     error::{InternalApiResult, RazorpayResult},
     ids::{CustomerId, OfferId, PlanId, SubscriptionId},
+    pagination::paginate,
     util::{deserialize_notes, serialize_bool_as_int_option},
     Razorpay,
 };
@@ -174,6 +176,7 @@ impl Subscription {
             .post(RequestParams {
                 url: "/subscriptions".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -193,6 +196,7 @@ impl Subscription {
             .get(RequestParams {
                 url: format!("/subscription/{}", subscription_id),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -203,18 +207,19 @@ impl Subscription {
         }
     }
 
-    pub async fn list<T>(
+    pub async fn list<'a, T>(
         razorpay: &Razorpay,
         params: T,
     ) -> RazorpayResult<Collection<Subscription>>
     where
-        T: for<'a> Into<Option<ListSubscriptions<'a>>>,
+        T: Into<Option<ListSubscriptions<'a>>>,
     {
         let res = razorpay
             .api
             .get(RequestParams {
                 url: "/subscription".to_owned(),
                 version: None,
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -225,6 +230,25 @@ impl Subscription {
         }
     }
 
+    pub fn list_stream<'a, T>(
+        razorpay: &'a Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Subscription>> + 'a
+    where
+        T: Into<Option<ListSubscriptions<'a>>>,
+    {
+        let params = params.into().unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Subscription::list(
+                razorpay,
+                ListSubscriptions {
+                    filter: Some(filter),
+                    ..params.clone()
+                },
+            )
+        })
+    }
+
     pub async fn cancel<T>(
         razorpay: &Razorpay,
         subscription_id: &SubscriptionId,
@@ -242,6 +266,7 @@ impl Subscription {
             .post(RequestParams {
                 url: format!("/subscription/{}/cancel", subscription_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({
                     "cancel_at_cycle_end": cancel_at_cycle_end,
                 })),
@@ -264,6 +289,7 @@ impl Subscription {
             .patch(RequestParams {
                 url: format!("/subscriptions/{}", subscription_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -286,6 +312,7 @@ impl Subscription {
                     subscription_id
                 ),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -308,6 +335,7 @@ impl Subscription {
                     subscription_id
                 ),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -327,6 +355,7 @@ impl Subscription {
             .post(RequestParams {
                 url: format!("/subscriptions/{}/pause", subscription_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({
                     "pause_at": "now",
                 })),
@@ -348,6 +377,7 @@ impl Subscription {
             .post(RequestParams {
                 url: format!("/subscriptions/{}/resume", subscription_id),
                 version: None,
+                idempotency_key: None,
                 data: Some(json!({
                     "resume_at": "now",
                 })),
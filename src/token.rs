@@ -0,0 +1,362 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::{Debug, Formatter, Result as FormatterResult};
+#[cfg(feature = "std")]
+use std::fmt::{Debug, Formatter, Result as FormatterResult};
+
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    api::RequestParams,
+    card::{CardNetwork, CardSubType, CardTypeExtended},
+    common::{Collection, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::{ServiceProviderTokenId, TokenId},
+    payment::PaymentMethod,
+    util::{deserialize_notes, mask_card_number},
+    CustomerId, PaymentId, Razorpay,
+};
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TokenCardFlows {
+    #[serde(default)]
+    pub otp: bool,
+    #[serde(default)]
+    pub recurring: bool,
+}
+
+/// The card behind a token, its number is never returned.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TokenCard {
+    pub name: Option<String>,
+    pub last4: String,
+    pub network: CardNetwork,
+    #[serde(rename = "type")]
+    pub type_: CardTypeExtended,
+    pub issuer: Option<String>,
+    #[serde(default)]
+    pub international: bool,
+    #[serde(default)]
+    pub emi: bool,
+    pub sub_type: Option<CardSubType>,
+    pub token_iin: Option<String>,
+    pub expiry_month: Option<String>,
+    pub expiry_year: Option<String>,
+    pub flows: Option<TokenCardFlows>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TokenVpa {
+    pub username: Option<String>,
+    pub handle: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenRecurringStatus {
+    Initiated,
+    Confirmed,
+    Rejected,
+    Cancelled,
+    Paused,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TokenRecurringDetails {
+    pub status: TokenRecurringStatus,
+    pub failure_reason: Option<String>,
+}
+
+/// A saved payment method of a customer, a card, a VPA or a mandate.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "token")]
+pub struct Token {
+    pub id: TokenId,
+    pub token: Option<String>,
+    pub method: PaymentMethod,
+    pub bank: Option<String>,
+    pub wallet: Option<String>,
+    pub card: Option<TokenCard>,
+    pub vpa: Option<TokenVpa>,
+    #[serde(default)]
+    pub recurring: bool,
+    pub recurring_details: Option<TokenRecurringDetails>,
+    pub auth_type: Option<String>,
+    pub mrn: Option<String>,
+    pub max_amount: Option<u64>,
+    #[serde(default)]
+    pub dcc_enabled: bool,
+    #[serde(default, deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub used_at: Option<DateTime<Utc>>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub expired_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CreateTokenMethod {
+    #[default]
+    Card,
+}
+
+#[derive(Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateTokenCard<'a> {
+    pub number: &'a str,
+    pub expiry_month: &'a str,
+    pub expiry_year: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvv: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+}
+
+impl Debug for CreateTokenCard<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        f.debug_struct("CreateTokenCard")
+            .field("number", &mask_card_number(self.number))
+            .field("expiry_month", &self.expiry_month)
+            .field("expiry_year", &self.expiry_year)
+            .field("cvv", &self.cvv.map(|_| "***"))
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Proof that the card holder authenticated the card, either a payment
+/// authenticated through Razorpay or the reference number of an
+/// authentication done elsewhere.
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateTokenAuthentication<'a> {
+    pub provider: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_reference_id: Option<&'a PaymentId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_reference_number: Option<&'a str>,
+}
+
+/// Creates a network token for a card, see [`Token::create`].
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateToken<'a> {
+    pub customer_id: &'a CustomerId,
+    pub method: CreateTokenMethod,
+    pub card: CreateTokenCard<'a>,
+    pub authentication: CreateTokenAuthentication<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct ServiceProviderTokenData {
+    pub token_reference_number: Option<String>,
+    pub payment_account_reference: Option<String>,
+    pub token_iin: Option<String>,
+    pub token_number: Option<String>,
+    pub token_expiry_month: Option<String>,
+    pub token_expiry_year: Option<String>,
+    pub cryptogram_value: Option<String>,
+}
+
+/// A token issued by a card network, used to process payments of a
+/// Razorpay token on another payment aggregator or gateway.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "service_provider_token")]
+pub struct ServiceProviderToken {
+    pub id: ServiceProviderTokenId,
+    pub provider_type: String,
+    pub provider_name: String,
+    pub interoperable: bool,
+    pub status: String,
+    pub provider_data: ServiceProviderTokenData,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteTokenResult {
+    deleted: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServiceProviderTokens {
+    service_provider_tokens: Vec<ServiceProviderToken>,
+}
+
+impl Token {
+    pub async fn list(
+        razorpay: &Razorpay,
+        customer_id: &CustomerId,
+    ) -> RazorpayResult<Collection<Token>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/customers/{}/tokens", customer_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(tokens) => Ok(tokens),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        customer_id: &CustomerId,
+        token_id: &TokenId,
+    ) -> RazorpayResult<Token> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/customers/{}/tokens/{}", customer_id, token_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(token) => Ok(token),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn delete(
+        razorpay: &Razorpay,
+        customer_id: &CustomerId,
+        token_id: &TokenId,
+    ) -> RazorpayResult<bool> {
+        let res: InternalApiResult<DeleteTokenResult> = razorpay
+            .api
+            .delete(RequestParams {
+                url: format!("/customers/{}/tokens/{}", customer_id, token_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(res) => Ok(res.deleted),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Tokenises a card with its network, for merchants using the
+    /// tokenisation service without Razorpay checkout.
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateToken<'_>,
+    ) -> RazorpayResult<Token> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/tokens".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(token) => Ok(token),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Fetches a network token created with [`Token::create`].
+    pub async fn fetch_network_token(
+        razorpay: &Razorpay,
+        token_id: &TokenId,
+    ) -> RazorpayResult<Token> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/tokens/fetch".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "id": token_id })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(token) => Ok(token),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Deletes a network token created with [`Token::create`].
+    pub async fn delete_network_token(
+        razorpay: &Razorpay,
+        token_id: &TokenId,
+    ) -> RazorpayResult<()> {
+        let res: InternalApiResult<Value> = razorpay
+            .api
+            .post(RequestParams {
+                url: "/tokens/delete".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "id": token_id })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(_) => Ok(()),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Fetches the network tokens needed to process a payment with a
+    /// network token on another payment aggregator or gateway.
+    pub async fn fetch_service_provider_tokens(
+        razorpay: &Razorpay,
+        token_id: &TokenId,
+    ) -> RazorpayResult<Vec<ServiceProviderToken>> {
+        let res: InternalApiResult<ServiceProviderTokens> = razorpay
+            .api
+            .post(RequestParams {
+                url: "/tokens/service_provider_tokens/token_transactional_data"
+                    .to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "id": token_id })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(res) => Ok(res.service_provider_tokens),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_hides_optional_cvv() {
+        let card = CreateTokenCard {
+            number: "4111111111111234",
+            expiry_month: "12",
+            expiry_year: "30",
+            cvv: Some("987"),
+            name: None,
+        };
+
+        assert!(format!("{:?}", card).contains(r#"cvv: Some("***")"#));
+        assert!(format!("{:?}", CreateTokenCard { cvv: None, ..card })
+            .contains("cvv: None"));
+    }
+}
@@ -0,0 +1,390 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Currency, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::{ReversalId, TransferId},
+    pagination::paginate,
+    settlement::Settlement,
+    util::deserialize_notes,
+    AccountId, OrderId, PaymentId, Razorpay, RefundId, SettlementId,
+};
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Created,
+    Pending,
+    Processed,
+    Failed,
+    Reversed,
+    PartiallyReversed,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferSettlementStatus {
+    Pending,
+    OnHold,
+    Settled,
+}
+
+/// The entity the funds of a transfer are taken from, direct transfers are
+/// made from the merchant's own account.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum TransferSource {
+    Payment(PaymentId),
+    Order(OrderId),
+    Account(AccountId),
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TransferError {
+    pub code: Option<String>,
+    pub description: Option<String>,
+    pub reason: Option<String>,
+    pub field: Option<String>,
+    pub step: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "transfer")]
+pub struct Transfer {
+    pub id: TransferId,
+    pub status: Option<TransferStatus>,
+    pub source: TransferSource,
+    pub recipient: AccountId,
+    pub amount: u64,
+    pub currency: Currency,
+    pub amount_reversed: u64,
+    pub fees: Option<u64>,
+    pub tax: Option<u64>,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(default)]
+    pub linked_account_notes: Vec<String>,
+    pub on_hold: bool,
+    #[serde(with = "ts_seconds_option", default)]
+    pub on_hold_until: Option<DateTime<Utc>>,
+    pub settlement_status: Option<TransferSettlementStatus>,
+    pub recipient_settlement_id: Option<SettlementId>,
+    /// Only present when listed with [`TransferExpand::RecipientSettlement`].
+    pub recipient_settlement: Option<Settlement>,
+    pub error: Option<TransferError>,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "reversal")]
+pub struct Reversal {
+    pub id: ReversalId,
+    pub transfer_id: TransferId,
+    pub amount: u64,
+    pub fee: u64,
+    pub tax: u64,
+    pub currency: Currency,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    pub initiator_id: Option<String>,
+    pub customer_refund_id: Option<RefundId>,
+    pub utr: Option<String>,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A transfer to a linked account, either made directly with
+/// [`Transfer::create`] or as part of a payment or an order.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateTransfer<'a> {
+    pub account: &'a AccountId,
+    pub amount: u64,
+    pub currency: Currency,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    /// Keys of `notes` that are shared with the linked account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linked_account_notes: Option<&'a [&'a str]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_hold: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub on_hold_until: Option<DateTime<Utc>>,
+    /// Only sent by [`Transfer::create`], ignored for the transfers of a
+    /// payment or an order.
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+/// Transfers made from a captured payment, see
+/// [`Transfer::create_from_payment`].
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreatePaymentTransfers<'a> {
+    pub transfers: &'a [CreateTransfer<'a>],
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct ReverseTransfer<'a> {
+    /// The amount reversed, the whole transfer if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct UpdateTransfer {
+    pub on_hold: bool,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub on_hold_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferExpand {
+    RecipientSettlement,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct ListTransfers<'a> {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_settlement_id: Option<&'a SettlementId>,
+    #[serde(rename = "expand[]")]
+    pub expand: &'a [TransferExpand],
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderTransfers {
+    transfers: Collection<Transfer>,
+}
+
+impl Transfer {
+    /// Transfers funds from the merchant's account balance to a linked
+    /// account.
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateTransfer<'_>,
+    ) -> RazorpayResult<Transfer> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/transfers".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(transfer) => Ok(transfer),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Splits a captured payment between linked accounts.
+    pub async fn create_from_payment(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+        params: CreatePaymentTransfers<'_>,
+    ) -> RazorpayResult<Collection<Transfer>> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/payments/{}/transfers", payment_id),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(transfers) => Ok(transfers),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        transfer_id: &TransferId,
+    ) -> RazorpayResult<Transfer> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/transfers/{}", transfer_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(transfer) => Ok(transfer),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list(
+        razorpay: &Razorpay,
+        params: Option<ListTransfers<'_>>,
+    ) -> RazorpayResult<Collection<Transfer>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/transfers".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(transfers) => Ok(transfers),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<'a>(
+        razorpay: &'a Razorpay,
+        params: Option<ListTransfers<'a>>,
+    ) -> impl Stream<Item = RazorpayResult<Transfer>> + 'a {
+        let params = params.unwrap_or_default();
+        paginate(params.filter.clone(), move |filter| {
+            Transfer::list(
+                razorpay,
+                Some(ListTransfers {
+                    filter: Some(filter),
+                    ..params.clone()
+                }),
+            )
+        })
+    }
+
+    pub async fn list_for_payment(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+    ) -> RazorpayResult<Collection<Transfer>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payments/{}/transfers", payment_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(transfers) => Ok(transfers),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Lists the transfers created with an order, see
+    /// [`CreateOrder::transfers`](crate::order::CreateOrder::transfers).
+    pub async fn list_for_order(
+        razorpay: &Razorpay,
+        order_id: &OrderId,
+    ) -> RazorpayResult<Collection<Transfer>> {
+        let res: InternalApiResult<OrderTransfers> = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/orders/{}", order_id),
+                version: None,
+                idempotency_key: None,
+                data: Some(json!({ "expand[]": ["transfers"] })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(res) => Ok(res.transfers),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Holds or releases the settlement of a transfer to the linked account.
+    pub async fn update(
+        razorpay: &Razorpay,
+        transfer_id: &TransferId,
+        params: UpdateTransfer,
+    ) -> RazorpayResult<Transfer> {
+        let res = razorpay
+            .api
+            .patch(RequestParams {
+                url: format!("/transfers/{}", transfer_id),
+                version: None,
+                idempotency_key: None,
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(transfer) => Ok(transfer),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Reverses a transfer, fully unless an amount is given.
+    pub async fn reverse(
+        razorpay: &Razorpay,
+        transfer_id: &TransferId,
+        params: ReverseTransfer<'_>,
+    ) -> RazorpayResult<Reversal> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!("/transfers/{}/reversals", transfer_id),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(reversal) => Ok(reversal),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list_reversals(
+        razorpay: &Razorpay,
+        transfer_id: &TransferId,
+    ) -> RazorpayResult<Collection<Reversal>> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/transfers/{}/reversals", transfer_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(reversals) => Ok(reversals),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
@@ -5,11 +5,18 @@ use core::fmt::{Debug, Display};
 #[cfg(feature = "std")]
 use std::fmt::{Debug, Display};
 
-use data_encoding::HEXLOWER;
-use ring::hmac;
-use serde::{Deserialize, Deserializer, Serializer};
+use chrono::{DateTime, Utc};
+use data_encoding::{HEXLOWER, HEXLOWER_PERMISSIVE};
+use ring::{
+    hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serializer};
 
-use crate::common::Object;
+use crate::{
+    common::Object,
+    ids::{OrderId, PaymentId, PaymentLinkId, SubscriptionId},
+};
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -45,6 +52,49 @@ where
     })
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ValueOrEmptyArray<T> {
+    Array([u8; 0]),
+    Value(T),
+}
+
+/// Deserializes a value which the API sends as `[]` when it is empty.
+pub(crate) fn deserialize_empty_array_as_none<'a, D, T>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'a>,
+    T: DeserializeOwned,
+{
+    let val: Option<ValueOrEmptyArray<T>> =
+        Deserialize::deserialize(deserializer)?;
+
+    Ok(match val {
+        Some(ValueOrEmptyArray::Value(val)) => Some(val),
+        Some(ValueOrEmptyArray::Array(_)) | None => None,
+    })
+}
+
+/// Deserializes a timestamp which the API sends as `0` when it is unset.
+pub(crate) fn deserialize_ts_seconds_or_zero<'a, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'a>,
+{
+    let val: Option<i64> = Deserialize::deserialize(deserializer)?;
+
+    match val {
+        None | Some(0) => Ok(None),
+        Some(secs) => {
+            DateTime::from_timestamp(secs, 0).map(Some).ok_or_else(|| {
+                serde::de::Error::custom(format!("invalid timestamp: {}", secs))
+            })
+        }
+    }
+}
+
 pub(crate) fn display_option<T>(option: Option<&T>) -> String
 where
     T: Display,
@@ -65,6 +115,96 @@ where
     }
 }
 
+/// Hides every digit of a card number but the last four, for `Debug`
+/// output.
+pub(crate) fn mask_card_number(number: &str) -> String {
+    let digits = number.chars().filter(char::is_ascii_digit).count();
+    let last4: String = number
+        .chars()
+        .filter(char::is_ascii_digit)
+        .skip(if digits > 4 { digits - 4 } else { digits })
+        .collect();
+
+    format!("****{}", last4)
+}
+
+pub(crate) fn verify_signature(
+    message: &[u8],
+    signature: &[u8],
+    secret: &[u8],
+) -> bool {
+    let signature = match HEXLOWER_PERMISSIVE.decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::verify(&key, message, &signature).is_ok()
+}
+
+/// Verifies the `razorpay_signature` returned by Standard Checkout for an
+/// order, `secret` being the key secret of the account.
+pub fn verify_payment_signature<T, U>(
+    order_id: &OrderId,
+    payment_id: &PaymentId,
+    signature: T,
+    secret: U,
+) -> bool
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    verify_signature(
+        format!("{}|{}", order_id, payment_id).as_bytes(),
+        signature.as_ref().as_bytes(),
+        secret.as_ref().as_bytes(),
+    )
+}
+
+/// Verifies the `razorpay_signature` returned by Checkout for the
+/// authorization payment of a subscription.
+pub fn verify_subscription_signature<T, U>(
+    payment_id: &PaymentId,
+    subscription_id: &SubscriptionId,
+    signature: T,
+    secret: U,
+) -> bool
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    verify_signature(
+        format!("{}|{}", payment_id, subscription_id).as_bytes(),
+        signature.as_ref().as_bytes(),
+        secret.as_ref().as_bytes(),
+    )
+}
+
+/// Verifies the `razorpay_signature` query parameter Razorpay appends to the
+/// `callback_url` of a payment link.
+pub fn verify_payment_link_signature<T, U>(
+    payment_link_id: &PaymentLinkId,
+    reference_id: &str,
+    status: &str,
+    payment_id: &PaymentId,
+    signature: T,
+    secret: U,
+) -> bool
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    verify_signature(
+        format!(
+            "{}|{}|{}|{}",
+            payment_link_id, reference_id, status, payment_id
+        )
+        .as_bytes(),
+        signature.as_ref().as_bytes(),
+        secret.as_ref().as_bytes(),
+    )
+}
+
 pub fn generate_webhook_signature<T, U>(body: T, secret: U) -> String
 where
     T: AsRef<str>,
@@ -77,3 +217,124 @@ where
     let expected_signature = hmac::sign(&key, body.as_bytes());
     HEXLOWER.encode(expected_signature.as_ref())
 }
+
+/// Generates a random key for the `idempotency_key` of mutating calls, e.g.
+/// `CreateRefund::idempotency_key`.
+///
+/// Persist the key alongside the operation if it may be re-attempted after a
+/// restart, Razorpay only deduplicates requests sent with the same key.
+pub fn generate_idempotency_key() -> String {
+    let mut bytes = [0; 16];
+    if SystemRandom::new().fill(&mut bytes).is_err() {
+        let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        bytes[..8].copy_from_slice(&nanos.to_le_bytes());
+    }
+
+    HEXLOWER.encode(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_all_but_the_last_four_digits() {
+        assert_eq!(mask_card_number("4111111111111234"), "****1234");
+        assert_eq!(mask_card_number("4111 1111 1111 1234"), "****1234");
+        assert_eq!(mask_card_number("4111-1111-1111-1234"), "****1234");
+        // too short to reveal any digits
+        assert_eq!(mask_card_number("1234"), "****");
+        assert_eq!(mask_card_number(""), "****");
+    }
+
+    const SECRET: &str = "key_secret";
+    // HMAC-SHA256 of `order_IEIaMR65cu6nz3|pay_IH4NVgf4Dreq1l` with `SECRET`
+    const PAYMENT_SIGNATURE: &str =
+        "04ca3cf2a204457889325d950bff630bdd09f9539eae372c76893585f7f4eee8";
+
+    fn verify_payment(order_id: &str, signature: &str, secret: &str) -> bool {
+        verify_payment_signature(
+            &order_id.parse().unwrap(),
+            &"pay_IH4NVgf4Dreq1l".parse().unwrap(),
+            signature,
+            secret,
+        )
+    }
+
+    #[test]
+    fn verifies_a_payment_signature() {
+        assert!(verify_payment(
+            "order_IEIaMR65cu6nz3",
+            PAYMENT_SIGNATURE,
+            SECRET
+        ));
+        // hex digits in any case are accepted
+        assert!(verify_payment(
+            "order_IEIaMR65cu6nz3",
+            &PAYMENT_SIGNATURE.to_uppercase(),
+            SECRET
+        ));
+    }
+
+    #[test]
+    fn rejects_a_payment_signature_for_other_data_or_secret() {
+        assert!(!verify_payment(
+            "order_IEIaMR65cu6nz4",
+            PAYMENT_SIGNATURE,
+            SECRET
+        ));
+        assert!(!verify_payment(
+            "order_IEIaMR65cu6nz3",
+            PAYMENT_SIGNATURE,
+            "other_secret"
+        ));
+    }
+
+    #[test]
+    fn rejects_a_malformed_signature() {
+        let truncated = &PAYMENT_SIGNATURE[..62];
+        let not_hex = PAYMENT_SIGNATURE.replace('a', "g");
+
+        for signature in ["", truncated, &not_hex, "not a signature"] {
+            assert!(
+                !verify_payment("order_IEIaMR65cu6nz3", signature, SECRET),
+                "{}",
+                signature
+            );
+        }
+    }
+
+    #[test]
+    fn verifies_a_subscription_signature() {
+        let verify = |subscription_id: &str| {
+            verify_subscription_signature(
+                &"pay_IH4NVgf4Dreq1l".parse().unwrap(),
+                &subscription_id.parse().unwrap(),
+                "f2a80178a06d1e5f71c584718f74c9917e2a2fc8531a693a3bb6c5ef16b2ea38",
+                SECRET,
+            )
+        };
+
+        assert!(verify("sub_IEKtBfPIqTHLWd"));
+        assert!(!verify("sub_IEKtBfPIqTHLWe"));
+    }
+
+    #[test]
+    fn verifies_a_payment_link_signature() {
+        // signed as `payment_link_id|reference_id|status|payment_id`, as
+        // documented by Razorpay
+        let verify = |status: &str| {
+            verify_payment_link_signature(
+                &"plink_IH3cNucfVEgV68".parse().unwrap(),
+                "TSsd1989",
+                status,
+                &"pay_IH4NVgf4Dreq1l".parse().unwrap(),
+                "004e96929a0142115f3682246c9338d31aa6f7099c4389efbfba1ec7d57d59b8",
+                SECRET,
+            )
+        };
+
+        assert!(verify("paid"));
+        assert!(!verify("partially_paid"));
+    }
+}
@@ -0,0 +1,509 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use chrono::{
+    serde::{ts_seconds, ts_seconds_option},
+    DateTime, Utc,
+};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    api::RequestParams,
+    common::{Collection, Filter, Object},
+    error::{InternalApiResult, RazorpayResult},
+    ids::{
+        BankAccountId, BankTransferId, UpiTransferId, VirtualAccountId, VpaId,
+    },
+    pagination::paginate,
+    payment::Payment,
+    util::deserialize_notes,
+    CustomerId, PaymentId, Razorpay,
+};
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VirtualAccountStatus {
+    Active,
+    Closed,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct BankAccount {
+    pub id: BankAccountId,
+    pub ifsc: String,
+    pub bank_name: Option<String>,
+    pub name: Option<String>,
+    pub account_number: String,
+    #[serde(default, deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct Vpa {
+    pub id: VpaId,
+    pub username: String,
+    pub handle: String,
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename_all = "snake_case")]
+pub enum VirtualAccountReceiver {
+    BankAccount(BankAccount),
+    Vpa(Vpa),
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowedPayerType {
+    BankAccount,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct AllowedPayerBankAccount {
+    pub ifsc: String,
+    pub account_number: String,
+}
+
+/// A payer allowed to pay into a virtual account with Third Party
+/// Validation (TPV), payments from any other account are refunded.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct AllowedPayer {
+    #[serde(rename = "type")]
+    pub type_: AllowedPayerType,
+    pub id: BankAccountId,
+    pub bank_account: AllowedPayerBankAccount,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "virtual_account")]
+pub struct VirtualAccount {
+    pub id: VirtualAccountId,
+    pub name: Option<String>,
+    pub status: VirtualAccountStatus,
+    pub description: Option<String>,
+    pub amount_expected: Option<u64>,
+    pub amount_paid: u64,
+    pub customer_id: Option<CustomerId>,
+    pub receivers: Vec<VirtualAccountReceiver>,
+    #[serde(default)]
+    pub allowed_payers: Vec<AllowedPayer>,
+    #[serde(deserialize_with = "deserialize_notes")]
+    pub notes: Object,
+    #[serde(with = "ts_seconds_option", default)]
+    pub close_by: Option<DateTime<Utc>>,
+    #[serde(with = "ts_seconds_option", default)]
+    pub closed_at: Option<DateTime<Utc>>,
+    #[serde(with = "ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "bank_transfer")]
+pub struct BankTransfer {
+    pub id: BankTransferId,
+    pub payment_id: PaymentId,
+    pub mode: String,
+    pub bank_reference: String,
+    pub amount: u64,
+    pub payer_bank_account: BankAccount,
+    pub virtual_account_id: VirtualAccountId,
+    pub virtual_account: Option<VirtualAccount>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "entity", rename = "upi_transfer")]
+pub struct UpiTransfer {
+    pub id: UpiTransferId,
+    pub payment_id: PaymentId,
+    pub amount: u64,
+    pub payer_vpa: Option<String>,
+    pub payer_bank: Option<String>,
+    pub payer_account: Option<String>,
+    pub payer_ifsc: Option<String>,
+    pub npci_reference_id: Option<String>,
+    pub virtual_account_id: VirtualAccountId,
+    pub virtual_account: Option<VirtualAccount>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VirtualAccountReceiverType {
+    BankAccount,
+    Vpa,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateVpaReceiver<'a> {
+    /// The part of the VPA after the merchant prefix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descriptor: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateVirtualAccountReceivers<'a> {
+    pub types: &'a [VirtualAccountReceiverType],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vpa: Option<CreateVpaReceiver<'a>>,
+    /// Only sent by [`VirtualAccount::add_receiver`], set it on
+    /// [`CreateVirtualAccount`] otherwise.
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateAllowedPayerBankAccount<'a> {
+    pub ifsc: &'a str,
+    pub account_number: &'a str,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CreateAllowedPayer<'a> {
+    BankAccount {
+        bank_account: CreateAllowedPayerBankAccount<'a>,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateVirtualAccount<'a> {
+    pub receivers: CreateVirtualAccountReceivers<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_payers: Option<Vec<CreateAllowedPayer<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<&'a CustomerId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_expected: Option<u64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "ts_seconds_option"
+    )]
+    pub close_by: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<Object>,
+    #[serde(skip)]
+    pub idempotency_key: Option<&'a str>,
+}
+
+impl VirtualAccount {
+    pub async fn create(
+        razorpay: &Razorpay,
+        params: CreateVirtualAccount<'_>,
+    ) -> RazorpayResult<VirtualAccount> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: "/virtual_accounts".to_owned(),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(virtual_account) => Ok(virtual_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn fetch(
+        razorpay: &Razorpay,
+        virtual_account_id: &VirtualAccountId,
+    ) -> RazorpayResult<VirtualAccount> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/virtual_accounts/{}", virtual_account_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(virtual_account) => Ok(virtual_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn list<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> RazorpayResult<Collection<VirtualAccount>>
+    where
+        T: Into<Option<Filter>>,
+    {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: "/virtual_accounts".to_owned(),
+                version: None,
+                idempotency_key: None,
+                data: params.into(),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(virtual_accounts) => Ok(virtual_accounts),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub fn list_stream<T>(
+        razorpay: &Razorpay,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<VirtualAccount>> + '_
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| {
+            VirtualAccount::list(razorpay, filter)
+        })
+    }
+
+    pub async fn list_payments<T>(
+        razorpay: &Razorpay,
+        virtual_account_id: &VirtualAccountId,
+        params: T,
+    ) -> RazorpayResult<Collection<Payment>>
+    where
+        T: Into<Option<Filter>>,
+    {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!(
+                    "/virtual_accounts/{}/payments",
+                    virtual_account_id
+                ),
+                version: None,
+                idempotency_key: None,
+                data: params.into(),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(payments) => Ok(payments),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Fetches the NEFT/RTGS/IMPS transfer behind a payment made to a
+    /// virtual account.
+    pub async fn fetch_bank_transfer(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+    ) -> RazorpayResult<BankTransfer> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payments/{}/bank_transfer", payment_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(bank_transfer) => Ok(bank_transfer),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    /// Fetches the UPI transfer behind a payment made to a virtual account.
+    pub async fn fetch_upi_transfer(
+        razorpay: &Razorpay,
+        payment_id: &PaymentId,
+    ) -> RazorpayResult<UpiTransfer> {
+        let res = razorpay
+            .api
+            .get(RequestParams {
+                url: format!("/payments/{}/upi_transfer", payment_id),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(upi_transfer) => Ok(upi_transfer),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn add_receiver(
+        razorpay: &Razorpay,
+        virtual_account_id: &VirtualAccountId,
+        params: CreateVirtualAccountReceivers<'_>,
+    ) -> RazorpayResult<VirtualAccount> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!(
+                    "/virtual_accounts/{}/receivers",
+                    virtual_account_id
+                ),
+                version: None,
+                idempotency_key: params.idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(virtual_account) => Ok(virtual_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn add_allowed_payer(
+        razorpay: &Razorpay,
+        virtual_account_id: &VirtualAccountId,
+        params: CreateAllowedPayer<'_>,
+        idempotency_key: Option<&str>,
+    ) -> RazorpayResult<VirtualAccount> {
+        let res = razorpay
+            .api
+            .post(RequestParams {
+                url: format!(
+                    "/virtual_accounts/{}/allowed_payers",
+                    virtual_account_id
+                ),
+                version: None,
+                idempotency_key: idempotency_key.map(Into::into),
+                data: Some(params),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(virtual_account) => Ok(virtual_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+
+    pub async fn delete_allowed_payer(
+        razorpay: &Razorpay,
+        virtual_account_id: &VirtualAccountId,
+        allowed_payer_id: &BankAccountId,
+    ) -> RazorpayResult<()> {
+        // answered with `204 No Content`, errors are still parsed from the
+        // body of a non-2xx response
+        razorpay
+            .api
+            .delete_raw(RequestParams {
+                url: format!(
+                    "/virtual_accounts/{}/allowed_payers/{}",
+                    virtual_account_id, allowed_payer_id
+                ),
+                version: None,
+                idempotency_key: None,
+                data: None::<()>,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn close(
+        razorpay: &Razorpay,
+        virtual_account_id: &VirtualAccountId,
+        idempotency_key: Option<&str>,
+    ) -> RazorpayResult<VirtualAccount> {
+        let res = razorpay
+            .api
+            .patch(RequestParams {
+                url: format!("/virtual_accounts/{}", virtual_account_id),
+                version: None,
+                idempotency_key: idempotency_key.map(Into::into),
+                data: Some(json!({ "status": "closed" })),
+            })
+            .await?;
+
+        match res {
+            InternalApiResult::Ok(virtual_account) => Ok(virtual_account),
+            InternalApiResult::Err { error } => Err(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::{error::RazorpayErrorKind, RetryPolicy};
+
+    // answers one request with `response` and returns its request line
+    async fn serve(
+        response: &'static str,
+    ) -> (Razorpay, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(response.as_bytes()).await.unwrap();
+            let request = String::from_utf8(request).unwrap();
+            request.lines().next().unwrap().to_owned()
+        });
+
+        let razorpay = Razorpay::builder("key", "secret")
+            .base_url(base_url)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        (razorpay, server)
+    }
+
+    #[tokio::test]
+    async fn deletes_allowed_payer_with_no_content() {
+        let (razorpay, server) =
+            serve("HTTP/1.1 204 No Content\r\nconnection: close\r\n\r\n").await;
+
+        VirtualAccount::delete_allowed_payer(
+            &razorpay,
+            &"va_1".parse().unwrap(),
+            &"ba_1".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            server.await.unwrap(),
+            "DELETE /v1/virtual_accounts/va_1/allowed_payers/ba_1 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_to_delete_allowed_payer_with_api_error() {
+        let (razorpay, server) = serve(
+            "HTTP/1.1 400 Bad Request\r\ncontent-type: application/json\r\n\
+             content-length: 63\r\nconnection: close\r\n\r\n\
+             {\"error\":{\"code\":\"BAD_REQUEST_ERROR\",\"description\":\"no payer\"}}",
+        )
+        .await;
+
+        let error = VirtualAccount::delete_allowed_payer(
+            &razorpay,
+            &"va_1".parse().unwrap(),
+            &"ba_1".parse().unwrap(),
+        )
+        .await
+        .unwrap_err();
+        server.await.unwrap();
+
+        assert_eq!(error.kind(), RazorpayErrorKind::BadRequest);
+    }
+}
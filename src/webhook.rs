@@ -1,20 +1,25 @@
 #[cfg(not(feature = "std"))]
 use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 #[cfg(not(feature = "std"))]
-use core::fmt::{Display, Formatter, Result as FormatterResult};
+use core::{
+    fmt::{Display, Formatter, Result as FormatterResult},
+    time::Duration,
+};
 #[cfg(feature = "std")]
 use std::{
     collections::HashMap,
     fmt::{Display, Formatter, Result as FormatterResult},
+    time::Duration,
 };
 
 use chrono::{
     serde::{ts_seconds, ts_seconds_option},
     DateTime, Utc,
 };
+use futures_core::Stream;
 #[cfg(not(feature = "std"))]
 use hashbrown::HashMap;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{
@@ -22,19 +27,35 @@ use crate::{
     api::RequestParams,
     dispute::Dispute,
     error::{InternalApiResult, RazorpayResult},
+    fund_account_validation::FundAccountValidation,
     invoice::Invoice,
     order::Order,
-    payment::Payment,
+    pagination::paginate,
+    payment::{Downtime, Payment},
+    payment_link::PaymentLink,
+    payout::Payout,
+    payout_link::PayoutLink,
     refund::Refund,
+    settlement::Settlement,
     subscription::Subscription,
-    util::generate_webhook_signature,
+    transfer::Transfer,
+    util::verify_signature,
+    virtual_account::{BankTransfer, UpiTransfer, VirtualAccount},
     AccountId, Collection, Filter, Razorpay,
 };
 
+/// The header carrying the signature of a webhook request.
+pub const SIGNATURE_HEADER: &str = "X-Razorpay-Signature";
+/// The header carrying the id of a webhook event, the same for every
+/// delivery of the event.
+pub const EVENT_ID_HEADER: &str = "X-Razorpay-Event-Id";
+
 #[derive(Debug)]
 pub enum WebhookError {
     ParseError(serde_json::error::Error),
     BadSignature,
+    StaleEvent { created_at: DateTime<Utc> },
+    MissingEntity(WebhookPayloadItemName),
 }
 
 impl Display for WebhookError {
@@ -44,6 +65,12 @@ impl Display for WebhookError {
                 write!(f, "Parsing error: {}", error)
             }
             WebhookError::BadSignature => write!(f, "Bad signature"),
+            WebhookError::StaleEvent { created_at } => {
+                write!(f, "Stale event created at {}", created_at)
+            }
+            WebhookError::MissingEntity(name) => {
+                write!(f, "Missing entity in payload: {:?}", name)
+            }
         }
     }
 }
@@ -264,8 +291,13 @@ pub enum WebhookPayloadItemName {
     Dispute,
     Invoice,
     Subscription,
+    Settlement,
+    #[serde(rename = "payment.downtime")]
+    PaymentDowntime,
     Transfer,
     VirtualAccount,
+    BankTransfer,
+    UpiTransfer,
     PaymentLink,
     #[serde(rename = "fund_account.validation")]
     FundAccountValidation,
@@ -287,6 +319,16 @@ pub enum WebhookPayloadItem {
     Dispute(Dispute),
     Invoice(Invoice),
     Subscription(Subscription),
+    Settlement(Settlement),
+    Downtime(Downtime),
+    PaymentLink(PaymentLink),
+    Transfer(Transfer),
+    VirtualAccount(VirtualAccount),
+    BankTransfer(BankTransfer),
+    UpiTransfer(UpiTransfer),
+    FundAccountValidation(FundAccountValidation),
+    Payout(Payout),
+    PayoutLink(PayoutLink),
     // TODO: Add missing webhook payload items
     //
     // the following items need to be implemented, the workaround for now is
@@ -294,12 +336,6 @@ pub enum WebhookPayloadItem {
     //        |
     //         "--------------------------------------------.
     //                                                       |
-    // Transfer(Transfer),                                   |
-    // VirtualAccount(VirtualAccount),                       |
-    // PaymentLink(PaymentLink),                             |
-    // FundAccountValidation(FundAccountValidation),         |
-    // Payout(Payout),                                       |
-    // PayoutLink(PayoutLink),                               |
     // MerchantProduct(MerchantProduct),                     |
     Account(Account),
     // PayoutDowntime(PayoutDowntime),                       |
@@ -349,6 +385,835 @@ pub struct WebhookEvent {
     pub created_at: DateTime<Utc>,
 }
 
+/// A [`WebhookEvent`] with the entities its [`EventType`] is documented to
+/// carry, see [`WebhookEvent::into_typed`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum TypedWebhookEvent {
+    PaymentAuthorized {
+        payment: Payment,
+    },
+    PaymentFailed {
+        payment: Payment,
+    },
+    PaymentCaptured {
+        payment: Payment,
+    },
+    PaymentDisputeCreated {
+        payment: Payment,
+        dispute: Dispute,
+    },
+    PaymentDisputeWon {
+        payment: Payment,
+        dispute: Dispute,
+    },
+    PaymentDisputeLost {
+        payment: Payment,
+        dispute: Dispute,
+    },
+    PaymentDisputeClosed {
+        payment: Payment,
+        dispute: Dispute,
+    },
+    PaymentDisputeUnderReview {
+        payment: Payment,
+        dispute: Dispute,
+    },
+    PaymentDisputeActionRequired {
+        payment: Payment,
+        dispute: Dispute,
+    },
+    PaymentDowntimeStarted {
+        downtime: Downtime,
+    },
+    PaymentDowntimeUpdated {
+        downtime: Downtime,
+    },
+    PaymentDowntimeResolved {
+        downtime: Downtime,
+    },
+    OrderPaid {
+        order: Order,
+        payment: Payment,
+    },
+    InvoicePaid {
+        invoice: Invoice,
+        order: Order,
+        payment: Payment,
+    },
+    InvoicePartiallyPaid {
+        invoice: Invoice,
+        order: Order,
+        payment: Payment,
+    },
+    InvoiceExpired {
+        invoice: Invoice,
+    },
+    SubscriptionAuthenticated {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionPaused {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionResumed {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionActivated {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionPending {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionHalted {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionCharged {
+        subscription: Subscription,
+        payment: Payment,
+    },
+    SubscriptionCancelled {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionCompleted {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SubscriptionUpdated {
+        subscription: Subscription,
+        payment: Option<Payment>,
+    },
+    SettlementProcessed {
+        settlement: Settlement,
+    },
+    VirtualAccountCredited {
+        virtual_account: VirtualAccount,
+        payment: Payment,
+        /// Set when the payment was made by a bank transfer.
+        bank_transfer: Option<BankTransfer>,
+        /// Set when the payment was made to a VPA receiver.
+        upi_transfer: Option<UpiTransfer>,
+    },
+    VirtualAccountCreated {
+        virtual_account: VirtualAccount,
+    },
+    VirtualAccountClosed {
+        virtual_account: VirtualAccount,
+    },
+    FundAccountValidationCompleted {
+        fund_account_validation: FundAccountValidation,
+    },
+    FundAccountValidationFailed {
+        fund_account_validation: FundAccountValidation,
+    },
+    PayoutProcessed {
+        payout: Payout,
+    },
+    PayoutReversed {
+        payout: Payout,
+    },
+    PayoutInitiated {
+        payout: Payout,
+    },
+    PayoutUpdated {
+        payout: Payout,
+    },
+    PayoutRejected {
+        payout: Payout,
+    },
+    PayoutPending {
+        payout: Payout,
+    },
+    PayoutQueued {
+        payout: Payout,
+    },
+    PayoutFailed {
+        payout: Payout,
+    },
+    PayoutDowntimeStarted {
+        payout_downtime: Value,
+    },
+    PayoutDowntimeResolved {
+        payout_downtime: Value,
+    },
+    RefundSpeedChanged {
+        refund: Refund,
+        payment: Payment,
+    },
+    RefundProcessed {
+        refund: Refund,
+        payment: Payment,
+    },
+    RefundFailed {
+        refund: Refund,
+        payment: Payment,
+    },
+    RefundCreated {
+        refund: Refund,
+        payment: Payment,
+    },
+    TransferProcessed {
+        transfer: Transfer,
+    },
+    TransferFailed {
+        transfer: Transfer,
+    },
+    AccountUnderReview {
+        account: Account,
+    },
+    AccountNeedsClarification {
+        account: Account,
+    },
+    AccountActivated {
+        account: Account,
+    },
+    AccountRejected {
+        account: Account,
+    },
+    AccountUpdated {
+        account: Account,
+    },
+    AccountSuspended {
+        account: Account,
+    },
+    AccountFundsHold {
+        account: Account,
+    },
+    AccountFundsUnhold {
+        account: Account,
+    },
+    AccountInstantlyActivated {
+        account: Account,
+    },
+    AccountPaymentsEnabled {
+        account: Account,
+    },
+    PaymentLinkPending {
+        payment_link: PaymentLink,
+    },
+    PaymentLinkPaid {
+        payment_link: PaymentLink,
+        order: Order,
+        payment: Payment,
+    },
+    PaymentLinkPartiallyPaid {
+        payment_link: PaymentLink,
+        order: Order,
+        payment: Payment,
+    },
+    PaymentLinkExpired {
+        payment_link: PaymentLink,
+    },
+    PaymentLinkCancelled {
+        payment_link: PaymentLink,
+    },
+    ProductRouteActivated {
+        merchant_product: Value,
+    },
+    ProductRouteUnderReview {
+        merchant_product: Value,
+    },
+    ProductRouteNeedsClarification {
+        merchant_product: Value,
+    },
+    ProductRouteRejected {
+        merchant_product: Value,
+    },
+    ProductPaymentGatewayActivated {
+        merchant_product: Value,
+    },
+    ProductPaymentGatewayUnderReview {
+        merchant_product: Value,
+    },
+    ProductPaymentGatewayNeedsClarification {
+        merchant_product: Value,
+    },
+    ProductPaymentGatewayRejected {
+        merchant_product: Value,
+    },
+    ProductPaymentGatewayActivatedKYCPending {
+        merchant_product: Value,
+    },
+    AccountAppAuthorizationRevoked,
+    PayoutLinkPending {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkIssued {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkProcessing {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkProcessed {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkAttempted {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkCancelled {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkRejected {
+        payout_link: PayoutLink,
+    },
+    PayoutLinkExpired {
+        payout_link: PayoutLink,
+    },
+    TransactionCreated {
+        transaction: Value,
+    },
+}
+
+trait WebhookEntity: DeserializeOwned {
+    fn from_item(item: WebhookPayloadItem) -> Option<Self>;
+}
+
+macro_rules! impl_webhook_entity {
+    ($($variant:ident($type:ty)),* $(,)?) => {
+        $(
+            impl WebhookEntity for $type {
+                fn from_item(item: WebhookPayloadItem) -> Option<Self> {
+                    match item {
+                        WebhookPayloadItem::$variant(entity) => Some(entity),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_webhook_entity!(
+    Order(Order),
+    Payment(Payment),
+    Refund(Refund),
+    Dispute(Dispute),
+    Invoice(Invoice),
+    Subscription(Subscription),
+    Settlement(Settlement),
+    Downtime(Downtime),
+    PaymentLink(PaymentLink),
+    Transfer(Transfer),
+    VirtualAccount(VirtualAccount),
+    BankTransfer(BankTransfer),
+    UpiTransfer(UpiTransfer),
+    FundAccountValidation(FundAccountValidation),
+    Payout(Payout),
+    PayoutLink(PayoutLink),
+    Account(Account),
+    Other(Value),
+);
+
+struct WebhookEntities(HashMap<WebhookPayloadItemName, WebhookPayload>);
+
+impl WebhookEntities {
+    fn optional_entity<T: WebhookEntity>(
+        &mut self,
+        name: WebhookPayloadItemName,
+    ) -> Result<Option<T>, WebhookError> {
+        let item = match self.0.remove(&name) {
+            Some(payload) => payload.entity,
+            None => return Ok(None),
+        };
+
+        match item {
+            // the untagged `WebhookPayloadItem` falls back to `Other` when
+            // the typed variant fails to parse, parse it again to surface
+            // the actual error
+            WebhookPayloadItem::Other(value) => {
+                Ok(Some(serde_json::from_value(value)?))
+            }
+            item => T::from_item(item)
+                .map(Some)
+                .ok_or(WebhookError::MissingEntity(name)),
+        }
+    }
+
+    fn entity<T: WebhookEntity>(
+        &mut self,
+        name: WebhookPayloadItemName,
+    ) -> Result<T, WebhookError> {
+        self.optional_entity(name.clone())?
+            .ok_or(WebhookError::MissingEntity(name))
+    }
+}
+
+impl WebhookEvent {
+    /// Extracts the entities of this event into the [`TypedWebhookEvent`]
+    /// variant matching its [`EventType`].
+    pub fn into_typed(self) -> Result<TypedWebhookEvent, WebhookError> {
+        let mut payload = WebhookEntities(self.payload);
+
+        Ok(match self.type_ {
+            EventType::PaymentAuthorized => {
+                TypedWebhookEvent::PaymentAuthorized {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::PaymentFailed => TypedWebhookEvent::PaymentFailed {
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::PaymentCaptured => TypedWebhookEvent::PaymentCaptured {
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::PaymentDisputeCreated => {
+                TypedWebhookEvent::PaymentDisputeCreated {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    dispute: payload.entity(WebhookPayloadItemName::Dispute)?,
+                }
+            }
+            EventType::PaymentDisputeWon => {
+                TypedWebhookEvent::PaymentDisputeWon {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    dispute: payload.entity(WebhookPayloadItemName::Dispute)?,
+                }
+            }
+            EventType::PaymentDisputeLost => {
+                TypedWebhookEvent::PaymentDisputeLost {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    dispute: payload.entity(WebhookPayloadItemName::Dispute)?,
+                }
+            }
+            EventType::PaymentDisputeClosed => {
+                TypedWebhookEvent::PaymentDisputeClosed {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    dispute: payload.entity(WebhookPayloadItemName::Dispute)?,
+                }
+            }
+            EventType::PaymentDisputeUnderReview => {
+                TypedWebhookEvent::PaymentDisputeUnderReview {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    dispute: payload.entity(WebhookPayloadItemName::Dispute)?,
+                }
+            }
+            EventType::PaymentDisputeActionRequired => {
+                TypedWebhookEvent::PaymentDisputeActionRequired {
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    dispute: payload.entity(WebhookPayloadItemName::Dispute)?,
+                }
+            }
+            EventType::PaymentDowntimeStarted => {
+                TypedWebhookEvent::PaymentDowntimeStarted {
+                    downtime: payload
+                        .entity(WebhookPayloadItemName::PaymentDowntime)?,
+                }
+            }
+            EventType::PaymentDowntimeUpdated => {
+                TypedWebhookEvent::PaymentDowntimeUpdated {
+                    downtime: payload
+                        .entity(WebhookPayloadItemName::PaymentDowntime)?,
+                }
+            }
+            EventType::PaymentDowntimeResolved => {
+                TypedWebhookEvent::PaymentDowntimeResolved {
+                    downtime: payload
+                        .entity(WebhookPayloadItemName::PaymentDowntime)?,
+                }
+            }
+            EventType::OrderPaid => TypedWebhookEvent::OrderPaid {
+                order: payload.entity(WebhookPayloadItemName::Order)?,
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::InvoicePaid => TypedWebhookEvent::InvoicePaid {
+                invoice: payload.entity(WebhookPayloadItemName::Invoice)?,
+                order: payload.entity(WebhookPayloadItemName::Order)?,
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::InvoicePartiallyPaid => {
+                TypedWebhookEvent::InvoicePartiallyPaid {
+                    invoice: payload.entity(WebhookPayloadItemName::Invoice)?,
+                    order: payload.entity(WebhookPayloadItemName::Order)?,
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::InvoiceExpired => TypedWebhookEvent::InvoiceExpired {
+                invoice: payload.entity(WebhookPayloadItemName::Invoice)?,
+            },
+            EventType::SubscriptionAuthenticated => {
+                TypedWebhookEvent::SubscriptionAuthenticated {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionPaused => {
+                TypedWebhookEvent::SubscriptionPaused {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionResumed => {
+                TypedWebhookEvent::SubscriptionResumed {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionActivated => {
+                TypedWebhookEvent::SubscriptionActivated {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionPending => {
+                TypedWebhookEvent::SubscriptionPending {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionHalted => {
+                TypedWebhookEvent::SubscriptionHalted {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionCharged => {
+                TypedWebhookEvent::SubscriptionCharged {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionCancelled => {
+                TypedWebhookEvent::SubscriptionCancelled {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionCompleted => {
+                TypedWebhookEvent::SubscriptionCompleted {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SubscriptionUpdated => {
+                TypedWebhookEvent::SubscriptionUpdated {
+                    subscription: payload
+                        .entity(WebhookPayloadItemName::Subscription)?,
+                    payment: payload
+                        .optional_entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::SettlementProcessed => {
+                TypedWebhookEvent::SettlementProcessed {
+                    settlement: payload
+                        .entity(WebhookPayloadItemName::Settlement)?,
+                }
+            }
+            EventType::VirtualAccountCredited => {
+                TypedWebhookEvent::VirtualAccountCredited {
+                    virtual_account: payload
+                        .entity(WebhookPayloadItemName::VirtualAccount)?,
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                    bank_transfer: payload.optional_entity(
+                        WebhookPayloadItemName::BankTransfer,
+                    )?,
+                    upi_transfer: payload
+                        .optional_entity(WebhookPayloadItemName::UpiTransfer)?,
+                }
+            }
+            EventType::VirtualAccountCreated => {
+                TypedWebhookEvent::VirtualAccountCreated {
+                    virtual_account: payload
+                        .entity(WebhookPayloadItemName::VirtualAccount)?,
+                }
+            }
+            EventType::VirtualAccountClosed => {
+                TypedWebhookEvent::VirtualAccountClosed {
+                    virtual_account: payload
+                        .entity(WebhookPayloadItemName::VirtualAccount)?,
+                }
+            }
+            EventType::FundAccountValidationCompleted => {
+                TypedWebhookEvent::FundAccountValidationCompleted {
+                    fund_account_validation: payload.entity(
+                        WebhookPayloadItemName::FundAccountValidation,
+                    )?,
+                }
+            }
+            EventType::FundAccountValidationFailed => {
+                TypedWebhookEvent::FundAccountValidationFailed {
+                    fund_account_validation: payload.entity(
+                        WebhookPayloadItemName::FundAccountValidation,
+                    )?,
+                }
+            }
+            EventType::PayoutProcessed => TypedWebhookEvent::PayoutProcessed {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutReversed => TypedWebhookEvent::PayoutReversed {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutInitiated => TypedWebhookEvent::PayoutInitiated {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutUpdated => TypedWebhookEvent::PayoutUpdated {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutRejected => TypedWebhookEvent::PayoutRejected {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutPending => TypedWebhookEvent::PayoutPending {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutQueued => TypedWebhookEvent::PayoutQueued {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutFailed => TypedWebhookEvent::PayoutFailed {
+                payout: payload.entity(WebhookPayloadItemName::Payout)?,
+            },
+            EventType::PayoutDowntimeStarted => {
+                TypedWebhookEvent::PayoutDowntimeStarted {
+                    payout_downtime: payload
+                        .entity(WebhookPayloadItemName::PayoutDowntime)?,
+                }
+            }
+            EventType::PayoutDowntimeResolved => {
+                TypedWebhookEvent::PayoutDowntimeResolved {
+                    payout_downtime: payload
+                        .entity(WebhookPayloadItemName::PayoutDowntime)?,
+                }
+            }
+            EventType::RefundSpeedChanged => {
+                TypedWebhookEvent::RefundSpeedChanged {
+                    refund: payload.entity(WebhookPayloadItemName::Refund)?,
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::RefundProcessed => TypedWebhookEvent::RefundProcessed {
+                refund: payload.entity(WebhookPayloadItemName::Refund)?,
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::RefundFailed => TypedWebhookEvent::RefundFailed {
+                refund: payload.entity(WebhookPayloadItemName::Refund)?,
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::RefundCreated => TypedWebhookEvent::RefundCreated {
+                refund: payload.entity(WebhookPayloadItemName::Refund)?,
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::TransferProcessed => {
+                TypedWebhookEvent::TransferProcessed {
+                    transfer: payload
+                        .entity(WebhookPayloadItemName::Transfer)?,
+                }
+            }
+            EventType::TransferFailed => TypedWebhookEvent::TransferFailed {
+                transfer: payload.entity(WebhookPayloadItemName::Transfer)?,
+            },
+            EventType::AccountUnderReview => {
+                TypedWebhookEvent::AccountUnderReview {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountNeedsClarification => {
+                TypedWebhookEvent::AccountNeedsClarification {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountActivated => {
+                TypedWebhookEvent::AccountActivated {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountRejected => TypedWebhookEvent::AccountRejected {
+                account: payload.entity(WebhookPayloadItemName::Account)?,
+            },
+            EventType::AccountUpdated => TypedWebhookEvent::AccountUpdated {
+                account: payload.entity(WebhookPayloadItemName::Account)?,
+            },
+            EventType::AccountSuspended => {
+                TypedWebhookEvent::AccountSuspended {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountFundsHold => {
+                TypedWebhookEvent::AccountFundsHold {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountFundsUnhold => {
+                TypedWebhookEvent::AccountFundsUnhold {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountInstantlyActivated => {
+                TypedWebhookEvent::AccountInstantlyActivated {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::AccountPaymentsEnabled => {
+                TypedWebhookEvent::AccountPaymentsEnabled {
+                    account: payload.entity(WebhookPayloadItemName::Account)?,
+                }
+            }
+            EventType::PaymentLinkPending => {
+                TypedWebhookEvent::PaymentLinkPending {
+                    payment_link: payload
+                        .entity(WebhookPayloadItemName::PaymentLink)?,
+                }
+            }
+            EventType::PaymentLinkPaid => TypedWebhookEvent::PaymentLinkPaid {
+                payment_link: payload
+                    .entity(WebhookPayloadItemName::PaymentLink)?,
+                order: payload.entity(WebhookPayloadItemName::Order)?,
+                payment: payload.entity(WebhookPayloadItemName::Payment)?,
+            },
+            EventType::PaymentLinkPartiallyPaid => {
+                TypedWebhookEvent::PaymentLinkPartiallyPaid {
+                    payment_link: payload
+                        .entity(WebhookPayloadItemName::PaymentLink)?,
+                    order: payload.entity(WebhookPayloadItemName::Order)?,
+                    payment: payload.entity(WebhookPayloadItemName::Payment)?,
+                }
+            }
+            EventType::PaymentLinkExpired => {
+                TypedWebhookEvent::PaymentLinkExpired {
+                    payment_link: payload
+                        .entity(WebhookPayloadItemName::PaymentLink)?,
+                }
+            }
+            EventType::PaymentLinkCancelled => {
+                TypedWebhookEvent::PaymentLinkCancelled {
+                    payment_link: payload
+                        .entity(WebhookPayloadItemName::PaymentLink)?,
+                }
+            }
+            EventType::ProductRouteActivated => {
+                TypedWebhookEvent::ProductRouteActivated {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductRouteUnderReview => {
+                TypedWebhookEvent::ProductRouteUnderReview {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductRouteNeedsClarification => {
+                TypedWebhookEvent::ProductRouteNeedsClarification {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductRouteRejected => {
+                TypedWebhookEvent::ProductRouteRejected {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductPaymentGatewayActivated => {
+                TypedWebhookEvent::ProductPaymentGatewayActivated {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductPaymentGatewayUnderReview => {
+                TypedWebhookEvent::ProductPaymentGatewayUnderReview {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductPaymentGatewayNeedsClarification => {
+                TypedWebhookEvent::ProductPaymentGatewayNeedsClarification {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductPaymentGatewayRejected => {
+                TypedWebhookEvent::ProductPaymentGatewayRejected {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::ProductPaymentGatewayActivatedKYCPending => {
+                TypedWebhookEvent::ProductPaymentGatewayActivatedKYCPending {
+                    merchant_product: payload
+                        .entity(WebhookPayloadItemName::MerchantProduct)?,
+                }
+            }
+            EventType::AccountAppAuthorizationRevoked => {
+                TypedWebhookEvent::AccountAppAuthorizationRevoked
+            }
+            EventType::PayoutLinkPending => {
+                TypedWebhookEvent::PayoutLinkPending {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkIssued => {
+                TypedWebhookEvent::PayoutLinkIssued {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkProcessing => {
+                TypedWebhookEvent::PayoutLinkProcessing {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkProcessed => {
+                TypedWebhookEvent::PayoutLinkProcessed {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkAttempted => {
+                TypedWebhookEvent::PayoutLinkAttempted {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkCancelled => {
+                TypedWebhookEvent::PayoutLinkCancelled {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkRejected => {
+                TypedWebhookEvent::PayoutLinkRejected {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::PayoutLinkExpired => {
+                TypedWebhookEvent::PayoutLinkExpired {
+                    payout_link: payload
+                        .entity(WebhookPayloadItemName::PayoutLink)?,
+                }
+            }
+            EventType::TransactionCreated => {
+                TypedWebhookEvent::TransactionCreated {
+                    transaction: payload
+                        .entity(WebhookPayloadItemName::Transaction)?,
+                }
+            }
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum WebhookOwnerType {
@@ -389,18 +1254,41 @@ pub struct UpdateWebhook<'a> {
 }
 
 impl Webhook {
-    // utility method
+    // utility methods
     pub fn construct_event(
         payload: &str,
         sig: &str,
         secret: &str,
     ) -> Result<WebhookEvent, WebhookError> {
-        let expected_sig = generate_webhook_signature(payload, secret);
-        if sig != expected_sig {
+        Self::construct_event_from_bytes(payload.as_bytes(), sig, secret, None)
+    }
+
+    /// Verifies the `X-Razorpay-Signature` of a raw request body and parses
+    /// it, rejecting events created more than `max_age` ago if given.
+    pub fn construct_event_from_bytes(
+        payload: &[u8],
+        sig: &str,
+        secret: &str,
+        max_age: Option<Duration>,
+    ) -> Result<WebhookEvent, WebhookError> {
+        if !verify_signature(payload, sig.as_bytes(), secret.as_bytes()) {
             return Err(WebhookError::BadSignature);
         }
 
-        Ok(serde_json::from_str(payload)?)
+        let event: WebhookEvent = serde_json::from_slice(payload)?;
+
+        if let Some(max_age) = max_age {
+            // events from the future (clock skew) count as fresh
+            let age =
+                (Utc::now() - event.created_at).to_std().unwrap_or_default();
+            if age > max_age {
+                return Err(WebhookError::StaleEvent {
+                    created_at: event.created_at,
+                });
+            }
+        }
+
+        Ok(event)
     }
 
     // APIs
@@ -414,6 +1302,7 @@ impl Webhook {
             .post(RequestParams {
                 url: format!("/accounts/{}/webhooks", account_id),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -437,6 +1326,7 @@ impl Webhook {
                     account_id, webhook_id
                 ),
                 version: None,
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -460,6 +1350,7 @@ impl Webhook {
             .get(RequestParams {
                 url: format!("/accounts/{}/webhooks", account_id),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: params.into(),
             })
             .await?;
@@ -470,6 +1361,19 @@ impl Webhook {
         }
     }
 
+    pub fn list_stream<'a, T>(
+        razorpay: &'a Razorpay,
+        account_id: &'a AccountId,
+        params: T,
+    ) -> impl Stream<Item = RazorpayResult<Webhook>> + 'a
+    where
+        T: Into<Option<Filter>>,
+    {
+        paginate(params.into(), move |filter| {
+            Webhook::list(razorpay, account_id, filter)
+        })
+    }
+
     pub async fn update(
         razorpay: &Razorpay,
         account_id: &AccountId,
@@ -484,6 +1388,7 @@ impl Webhook {
                     account_id, webhook_id
                 ),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: Some(params),
             })
             .await?;
@@ -507,6 +1412,7 @@ impl Webhook {
                     account_id, webhook_id
                 ),
                 version: Some("v2".to_owned()),
+                idempotency_key: None,
                 data: None::<()>,
             })
             .await?;
@@ -517,3 +1423,433 @@ impl Webhook {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        payment::{PaymentMethod, PaymentRefundStatus},
+        payment_link::PaymentLinkStatus,
+        refund::RefundSpeed,
+        subscription::SubscriptionStatus,
+        util::generate_webhook_signature,
+    };
+
+    // builds an event the way Razorpay sends it, with every entity wrapped
+    // in `{ "entity": ... }`
+    fn event(type_: &str, entities: Value) -> WebhookEvent {
+        let entities = entities.as_object().unwrap();
+        let payload: serde_json::Map<String, Value> = entities
+            .iter()
+            .map(|(name, entity)| (name.clone(), json!({ "entity": entity })))
+            .collect();
+
+        serde_json::from_value(json!({
+            "entity": "event",
+            "account_id": "acc_BFQ7uQEaa7j2z7",
+            "event": type_,
+            "contains": entities.keys().collect::<Vec<_>>(),
+            "payload": payload,
+            "created_at": 1567674606,
+        }))
+        .unwrap()
+    }
+
+    fn payment(method: &str, extra: Value) -> Value {
+        let mut payment = json!({
+            "id": "pay_DESlfW9H8K9uqM",
+            "entity": "payment",
+            "amount": 100,
+            "currency": "INR",
+            "status": "captured",
+            "order_id": "order_DESlLckIVRkHWj",
+            "invoice_id": null,
+            "international": false,
+            "method": method,
+            "amount_refunded": 0,
+            "refund_status": null,
+            "captured": true,
+            "description": null,
+            "card_id": null,
+            "bank": null,
+            "wallet": null,
+            "vpa": null,
+            "email": "gaurav.kumar@example.com",
+            "contact": "+919876543210",
+            "notes": [],
+            "fee": 2,
+            "tax": 0,
+            "error_code": null,
+            "error_description": null,
+            "error_source": null,
+            "error_step": null,
+            "error_reason": null,
+            "created_at": 1567674599
+        });
+        payment
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        payment
+    }
+
+    #[test]
+    fn types_payment_captured() {
+        let event = event(
+            "payment.captured",
+            json!({
+                "payment": payment("card", json!({
+                    "card_id": "card_DESlfW9H8K9uqM",
+                    "card": {
+                        "id": "card_DESlfW9H8K9uqM",
+                        "entity": "card",
+                        "name": "Gaurav Kumar",
+                        "last4": "1111",
+                        "network": "Visa",
+                        "type": "debit",
+                        "issuer": null,
+                        "international": false,
+                        "emi": false,
+                        "sub_type": "consumer"
+                    },
+                    "acquirer_data": { "auth_code": "828553" }
+                })),
+            }),
+        );
+
+        match event.into_typed().unwrap() {
+            TypedWebhookEvent::PaymentCaptured { payment } => {
+                assert_eq!(payment.id.as_str(), "pay_DESlfW9H8K9uqM");
+                assert_eq!(payment.card.unwrap().last4, "1111");
+                assert_eq!(
+                    payment.acquirer_data.unwrap().auth_code.as_deref(),
+                    Some("828553")
+                );
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn types_refund_processed() {
+        let event = event(
+            "refund.processed",
+            json!({
+                "refund": {
+                    "id": "rfnd_FP8QHiV938haTz",
+                    "entity": "refund",
+                    "amount": 100,
+                    "currency": "INR",
+                    "payment_id": "pay_DESlfW9H8K9uqM",
+                    "notes": [],
+                    "receipt": null,
+                    "acquirer_data": { "arn": null },
+                    "created_at": 1597735622,
+                    "batch_id": null,
+                    "status": "processed",
+                    "speed_processed": "normal",
+                    "speed_requested": "optimum"
+                },
+                "payment": payment("netbanking", json!({
+                    "status": "refunded",
+                    "bank": "HDFC",
+                    "amount_refunded": 100,
+                    "refund_status": "full",
+                    "acquirer_data": { "bank_transaction_id": "0125836177" }
+                })),
+            }),
+        );
+
+        match event.into_typed().unwrap() {
+            TypedWebhookEvent::RefundProcessed { refund, payment } => {
+                assert_eq!(refund.payment_id, payment.id);
+                assert_eq!(refund.speed_requested, Some(RefundSpeed::Optimum));
+                assert_eq!(
+                    payment.refund_status,
+                    Some(PaymentRefundStatus::Full)
+                );
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn types_virtual_account_credited() {
+        let virtual_account = json!({
+            "id": "va_DETPA6B0yCbrJy",
+            "name": "Acme Corp",
+            "entity": "virtual_account",
+            "status": "active",
+            "description": "Virtual Account for Gaurav Kumar",
+            "amount_expected": null,
+            "notes": [],
+            "amount_paid": 10000,
+            "customer_id": "cust_DETPA5nQhOyWyb",
+            "receivers": [{
+                "id": "ba_DETPA6OjbzBzEz",
+                "entity": "bank_account",
+                "ifsc": "RAZR0000001",
+                "bank_name": null,
+                "name": "Acme Corp",
+                "notes": [],
+                "account_number": "1112220051525627"
+            }],
+            "close_by": null,
+            "closed_at": null,
+            "created_at": 1567674559
+        });
+        let event = event(
+            "virtual_account.credited",
+            json!({
+                "payment": payment("bank_transfer", json!({
+                    "id": "pay_DETPqrZyVLTiBK",
+                    "amount": 10000,
+                    "order_id": null,
+                    "customer_id": "cust_DETPA5nQhOyWyb",
+                    "description": "NEFT payment"
+                })),
+                "virtual_account": virtual_account,
+                "bank_transfer": {
+                    "id": "bt_DETPqs7EzBJQjJ",
+                    "entity": "bank_transfer",
+                    "payment_id": "pay_DETPqrZyVLTiBK",
+                    "mode": "NEFT",
+                    "bank_reference": "157415",
+                    "amount": 10000,
+                    "payer_bank_account": {
+                        "id": "ba_DETPqsG1xWmvWL",
+                        "entity": "bank_account",
+                        "ifsc": "HDFC0000053",
+                        "bank_name": "HDFC Bank",
+                        "name": "Gaurav Kumar",
+                        "notes": [],
+                        "account_number": "765432123456789"
+                    },
+                    "virtual_account_id": "va_DETPA6B0yCbrJy",
+                    "virtual_account": virtual_account
+                },
+            }),
+        );
+
+        match event.into_typed().unwrap() {
+            TypedWebhookEvent::VirtualAccountCredited {
+                virtual_account,
+                payment,
+                bank_transfer: Some(bank_transfer),
+                upi_transfer: None,
+            } => {
+                assert_eq!(payment.method, PaymentMethod::BankTransfer);
+                assert_eq!(payment.order_id, None);
+                assert_eq!(bank_transfer.payment_id, payment.id);
+                assert_eq!(
+                    bank_transfer.virtual_account_id,
+                    virtual_account.id
+                );
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn types_payment_link_paid() {
+        let event = event(
+            "payment_link.paid",
+            json!({
+                "payment_link": {
+                    "accept_partial": false,
+                    "amount": 1000,
+                    "amount_paid": 1000,
+                    "cancelled_at": 0,
+                    "created_at": 1602523432,
+                    "currency": "INR",
+                    "customer": {
+                        "contact": "+919999999999",
+                        "email": "gaurav.kumar@example.com",
+                        "name": "Gaurav Kumar"
+                    },
+                    "description": "Payment for policy no #23456",
+                    "expire_by": 0,
+                    "expired_at": 0,
+                    "first_min_partial_amount": 0,
+                    "id": "plink_FpCtAUubQbrFDy",
+                    "notes": { "policy_name": "Jeevan Bima" },
+                    "notify": { "email": true, "sms": true },
+                    "order_id": "order_FpCtAVS6mqlEDF",
+                    "reference_id": "TSsd1989",
+                    "reminder_enable": true,
+                    "reminders": { "status": "in_progress" },
+                    "short_url": "https://rzp.io/i/ORTCZ1Jp4",
+                    "status": "paid",
+                    "updated_at": 1602523453,
+                    "upi_link": false,
+                    "user_id": ""
+                },
+                "order": {
+                    "amount": 1000,
+                    "amount_due": 0,
+                    "amount_paid": 1000,
+                    "attempts": 1,
+                    "created_at": 1602523432,
+                    "currency": "INR",
+                    "entity": "order",
+                    "id": "order_FpCtAVS6mqlEDF",
+                    "notes": { "policy_name": "Jeevan Bima" },
+                    "offer_id": null,
+                    "receipt": "TSsd1989",
+                    "status": "paid"
+                },
+                "payment": payment("upi", json!({
+                    "id": "pay_FpCtPqE7ZB8vG9",
+                    "amount": 1000,
+                    "order_id": "order_FpCtAVS6mqlEDF",
+                    "vpa": "gaurav.kumar@exampleupi",
+                    "acquirer_data": { "rrn": "029818375440" }
+                })),
+            }),
+        );
+
+        // parsed as is rather than through the `Other` fallback
+        assert!(matches!(
+            event.payload[&WebhookPayloadItemName::PaymentLink].entity,
+            WebhookPayloadItem::PaymentLink(_)
+        ));
+
+        match event.into_typed().unwrap() {
+            TypedWebhookEvent::PaymentLinkPaid {
+                payment_link,
+                order,
+                payment,
+            } => {
+                assert_eq!(payment_link.status, PaymentLinkStatus::Paid);
+                assert_eq!(payment_link.order_id, Some(order.id.clone()));
+                assert_eq!(payment_link.expire_by, None);
+                assert_eq!(payment.order_id, Some(order.id));
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn types_subscription_cancelled_without_payment() {
+        let event = event(
+            "subscription.cancelled",
+            json!({
+                "subscription": {
+                    "id": "sub_EI9F1ASlY9fZLc",
+                    "entity": "subscription",
+                    "plan_id": "plan_EI9EzKAwaYGN5N",
+                    "customer_id": "cust_D00000000000001",
+                    "status": "cancelled",
+                    "type": 2,
+                    "current_start": 1580284732,
+                    "current_end": 1580841000,
+                    "ended_at": 1580284732,
+                    "quantity": 1,
+                    "notes": [],
+                    "charge_at": null,
+                    "start_at": 1580284732,
+                    "end_at": 1583433000,
+                    "auth_attempts": 0,
+                    "total_count": 6,
+                    "paid_count": 1,
+                    "customer_notify": true,
+                    "created_at": 1580284702,
+                    "expire_by": 1580626111,
+                    "short_url": "https://rzp.io/i/fFWTkbf",
+                    "has_scheduled_changes": false,
+                    "change_scheduled_at": null,
+                    "source": "api",
+                    "payment_method": "card",
+                    "offer_id": null,
+                    "remaining_count": 5
+                },
+            }),
+        );
+
+        match event.into_typed().unwrap() {
+            TypedWebhookEvent::SubscriptionCancelled {
+                subscription,
+                payment: None,
+            } => {
+                assert_eq!(subscription.status, SubscriptionStatus::Cancelled);
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn reports_a_missing_entity() {
+        let event = event(
+            "refund.processed",
+            json!({ "payment": payment("netbanking", json!({})) }),
+        );
+
+        assert!(matches!(
+            event.into_typed(),
+            Err(WebhookError::MissingEntity(WebhookPayloadItemName::Refund))
+        ));
+    }
+
+    const SECRET: &str = "webhook_secret";
+
+    fn created(seconds_ago: i64) -> (String, String) {
+        let created_at = Utc::now().timestamp() - seconds_ago;
+        let payload = format!(
+            r#"{{"entity":"event","account_id":"acc_1","event":"order.paid","contains":[],"payload":{{}},"created_at":{}}}"#,
+            created_at
+        );
+        let signature = generate_webhook_signature(&payload, SECRET);
+        (payload, signature)
+    }
+
+    fn construct(
+        (payload, signature): &(String, String),
+        max_age: Duration,
+    ) -> Result<WebhookEvent, WebhookError> {
+        Webhook::construct_event_from_bytes(
+            payload.as_bytes(),
+            signature,
+            SECRET,
+            Some(max_age),
+        )
+    }
+
+    #[test]
+    fn accepts_an_event_within_max_age() {
+        let max_age = Duration::from_secs(300);
+
+        assert!(construct(&created(240), max_age).is_ok());
+        // clock skew puts the event in the future
+        assert!(construct(&created(-60), max_age).is_ok());
+    }
+
+    #[test]
+    fn rejects_an_event_older_than_max_age() {
+        let request = created(360);
+
+        assert!(matches!(
+            construct(&request, Duration::from_secs(300)),
+            Err(WebhookError::StaleEvent { .. })
+        ));
+        assert!(
+            Webhook::construct_event(&request.0, &request.1, SECRET).is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_a_bad_signature() {
+        let (payload, signature) = created(0);
+        let tampered = payload.replace("order.paid", "payment.captured");
+
+        assert!(matches!(
+            construct(&(tampered, signature.clone()), Duration::from_secs(300)),
+            Err(WebhookError::BadSignature)
+        ));
+        assert!(matches!(
+            Webhook::construct_event(&payload, &signature, "other_secret"),
+            Err(WebhookError::BadSignature)
+        ));
+    }
+}
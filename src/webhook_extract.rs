@@ -0,0 +1,359 @@
+//! Request extractors that verify Razorpay webhooks inside `axum` and
+//! `actix-web` handlers.
+//!
+//! Register a [`WebhookVerifier`] with the application (axum state through
+//! `FromRef`, actix-web `app_data` or `web::Data`) and take a
+//! [`VerifiedWebhook`] as a handler argument. Requests with a missing or bad
+//! signature, or a body that does not parse, are rejected with
+//! `400 Bad Request` before the handler runs.
+
+use std::{
+    fmt::{Display, Formatter, Result as FormatterResult},
+    time::Duration,
+};
+
+use crate::webhook::{Webhook, WebhookError, WebhookEvent};
+
+/// The webhook secret, and optionally the maximum accepted event age, used
+/// to verify incoming webhook requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookVerifier {
+    secret: String,
+    max_age: Option<Duration>,
+}
+
+impl WebhookVerifier {
+    pub fn new<T: Into<String>>(secret: T) -> Self {
+        Self {
+            secret: secret.into(),
+            max_age: None,
+        }
+    }
+
+    /// Rejects events created more than `max_age` ago.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn verify(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+        event_id: Option<String>,
+    ) -> Result<VerifiedWebhook, WebhookRejection> {
+        let signature = signature
+            .ok_or(WebhookRejection::Invalid(WebhookError::BadSignature))?;
+        let event = Webhook::construct_event_from_bytes(
+            payload,
+            signature,
+            &self.secret,
+            self.max_age,
+        )
+        .map_err(WebhookRejection::Invalid)?;
+
+        Ok(VerifiedWebhook { event, event_id })
+    }
+}
+
+/// A webhook event whose signature has been verified.
+#[derive(Debug, Clone)]
+pub struct VerifiedWebhook {
+    pub event: WebhookEvent,
+    /// The `X-Razorpay-Event-Id` header, used to deduplicate deliveries.
+    pub event_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum WebhookRejection {
+    /// No [`WebhookVerifier`] was registered with the application.
+    MissingVerifier,
+    /// The request body could not be read.
+    Body(String),
+    Invalid(WebhookError),
+}
+
+impl WebhookRejection {
+    pub fn status_code(&self) -> u16 {
+        match self {
+            WebhookRejection::MissingVerifier => 500,
+            WebhookRejection::Body(_) | WebhookRejection::Invalid(_) => 400,
+        }
+    }
+}
+
+impl Display for WebhookRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        match self {
+            WebhookRejection::MissingVerifier => {
+                write!(f, "No webhook verifier configured")
+            }
+            WebhookRejection::Body(error) => {
+                write!(f, "Could not read webhook body: {}", error)
+            }
+            WebhookRejection::Invalid(error) => {
+                write!(f, "Invalid webhook: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebhookRejection {}
+
+#[cfg(feature = "axum")]
+mod axum_impl {
+    use axum::{
+        body::Bytes,
+        extract::{FromRef, FromRequest, Request},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+
+    use super::{VerifiedWebhook, WebhookRejection, WebhookVerifier};
+    use crate::webhook::{EVENT_ID_HEADER, SIGNATURE_HEADER};
+
+    impl<S> FromRequest<S> for VerifiedWebhook
+    where
+        S: Send + Sync,
+        WebhookVerifier: FromRef<S>,
+    {
+        type Rejection = WebhookRejection;
+
+        async fn from_request(
+            req: Request,
+            state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            let verifier = WebhookVerifier::from_ref(state);
+            let headers = req.headers();
+            let signature = headers
+                .get(SIGNATURE_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+            let event_id = headers
+                .get(EVENT_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+
+            let body = Bytes::from_request(req, state)
+                .await
+                .map_err(|error| WebhookRejection::Body(error.body_text()))?;
+
+            verifier.verify(&body, signature.as_deref(), event_id)
+        }
+    }
+
+    impl IntoResponse for WebhookRejection {
+        fn into_response(self) -> Response {
+            let status = StatusCode::from_u16(self.status_code())
+                .unwrap_or(StatusCode::BAD_REQUEST);
+            (status, self.to_string()).into_response()
+        }
+    }
+}
+
+#[cfg(feature = "actix-web")]
+mod actix_impl {
+    use std::{future::Future, pin::Pin};
+
+    use actix_web::{
+        dev::Payload, http::StatusCode, web, FromRequest, HttpRequest,
+        HttpResponse, ResponseError,
+    };
+
+    use super::{VerifiedWebhook, WebhookRejection, WebhookVerifier};
+    use crate::webhook::{EVENT_ID_HEADER, SIGNATURE_HEADER};
+
+    impl FromRequest for VerifiedWebhook {
+        type Error = WebhookRejection;
+        type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+        fn from_request(
+            req: &HttpRequest,
+            payload: &mut Payload,
+        ) -> Self::Future {
+            let verifier = req
+                .app_data::<WebhookVerifier>()
+                .or_else(|| {
+                    req.app_data::<web::Data<WebhookVerifier>>()
+                        .map(|data| data.get_ref())
+                })
+                .cloned();
+            let headers = req.headers();
+            let signature = headers
+                .get(SIGNATURE_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+            let event_id = headers
+                .get(EVENT_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+            let body = web::Bytes::from_request(req, payload);
+
+            Box::pin(async move {
+                let verifier =
+                    verifier.ok_or(WebhookRejection::MissingVerifier)?;
+                let body = body.await.map_err(|error| {
+                    WebhookRejection::Body(error.to_string())
+                })?;
+
+                verifier.verify(&body, signature.as_deref(), event_id)
+            })
+        }
+    }
+
+    impl ResponseError for WebhookRejection {
+        fn status_code(&self) -> StatusCode {
+            StatusCode::from_u16(WebhookRejection::status_code(self))
+                .unwrap_or(StatusCode::BAD_REQUEST)
+        }
+
+        fn error_response(&self) -> HttpResponse {
+            HttpResponse::build(ResponseError::status_code(self))
+                .body(self.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::util::generate_webhook_signature;
+
+    const SECRET: &str = "webhook_secret";
+
+    fn payload() -> String {
+        format!(
+            r#"{{"entity":"event","account_id":"acc_1","event":"order.paid","contains":[],"payload":{{}},"created_at":{}}}"#,
+            Utc::now().timestamp()
+        )
+    }
+
+    #[test]
+    fn maps_rejections_to_status_codes() {
+        assert_eq!(WebhookRejection::MissingVerifier.status_code(), 500);
+        assert_eq!(WebhookRejection::Body("closed".into()).status_code(), 400);
+        assert_eq!(
+            WebhookRejection::Invalid(WebhookError::BadSignature).status_code(),
+            400
+        );
+    }
+
+    #[cfg(feature = "axum")]
+    mod axum_extractor {
+        use axum::{
+            body::Body,
+            extract::FromRequest,
+            http::{Request, StatusCode},
+            response::IntoResponse,
+        };
+
+        use super::*;
+        use crate::webhook::{EVENT_ID_HEADER, SIGNATURE_HEADER};
+
+        async fn extract(
+            signature: Option<String>,
+        ) -> Result<VerifiedWebhook, WebhookRejection> {
+            let payload = payload();
+            let mut req =
+                Request::post("/webhooks").header(EVENT_ID_HEADER, "ev_1");
+            if let Some(signature) = signature {
+                req = req.header(SIGNATURE_HEADER, signature);
+            }
+            let req = req.body(Body::from(payload)).unwrap();
+
+            VerifiedWebhook::from_request(req, &WebhookVerifier::new(SECRET))
+                .await
+        }
+
+        #[tokio::test]
+        async fn extracts_a_verified_webhook() {
+            let signature = generate_webhook_signature(payload(), SECRET);
+
+            let webhook = extract(Some(signature)).await.unwrap();
+
+            assert_eq!(webhook.event_id.as_deref(), Some("ev_1"));
+        }
+
+        #[tokio::test]
+        async fn rejects_a_missing_signature() {
+            let rejection = extract(None).await.unwrap_err();
+
+            assert_eq!(
+                rejection.into_response().status(),
+                StatusCode::BAD_REQUEST
+            );
+        }
+
+        // axum requires the verifier in the state at compile time, only the
+        // response of the rejection is left to check
+        #[test]
+        fn responds_to_a_missing_verifier_with_500() {
+            assert_eq!(
+                WebhookRejection::MissingVerifier.into_response().status(),
+                StatusCode::INTERNAL_SERVER_ERROR
+            );
+        }
+    }
+
+    #[cfg(feature = "actix-web")]
+    mod actix_extractor {
+        use actix_web::{
+            http::StatusCode, test::TestRequest, FromRequest, ResponseError,
+        };
+
+        use super::*;
+        use crate::webhook::{EVENT_ID_HEADER, SIGNATURE_HEADER};
+
+        async fn extract(
+            req: TestRequest,
+        ) -> Result<VerifiedWebhook, WebhookRejection> {
+            let (req, mut payload) = req
+                .insert_header((EVENT_ID_HEADER, "ev_1"))
+                .set_payload(payload())
+                .to_http_parts();
+
+            VerifiedWebhook::from_request(&req, &mut payload).await
+        }
+
+        #[tokio::test]
+        async fn extracts_a_verified_webhook() {
+            let signature = generate_webhook_signature(payload(), SECRET);
+            let req = TestRequest::post()
+                .app_data(WebhookVerifier::new(SECRET))
+                .insert_header((SIGNATURE_HEADER, signature));
+
+            let webhook = extract(req).await.unwrap();
+
+            assert_eq!(webhook.event_id.as_deref(), Some("ev_1"));
+        }
+
+        #[tokio::test]
+        async fn rejects_a_missing_signature() {
+            let req =
+                TestRequest::post().app_data(WebhookVerifier::new(SECRET));
+
+            let rejection = extract(req).await.unwrap_err();
+
+            assert_eq!(
+                ResponseError::status_code(&rejection),
+                StatusCode::BAD_REQUEST
+            );
+        }
+
+        #[tokio::test]
+        async fn rejects_a_missing_verifier() {
+            let signature = generate_webhook_signature(payload(), SECRET);
+            let req = TestRequest::post()
+                .insert_header((SIGNATURE_HEADER, signature));
+
+            let rejection = extract(req).await.unwrap_err();
+
+            assert_eq!(
+                ResponseError::status_code(&rejection),
+                StatusCode::INTERNAL_SERVER_ERROR
+            );
+        }
+    }
+}
@@ -0,0 +1,435 @@
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::{
+    fmt::{Debug, Display, Formatter, Result as FormatterResult},
+    future::Future,
+    pin::Pin,
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::{
+    fmt::{Debug, Display, Formatter, Result as FormatterResult},
+    future::Future,
+    pin::Pin,
+    time::Duration,
+};
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::webhook::{EventType, Webhook, WebhookError, WebhookEvent};
+#[cfg(feature = "std")]
+use crate::webhook_store::{WebhookEventClaim, WebhookEventStore};
+
+type HandlerFuture =
+    Pin<Box<dyn Future<Output = Result<(), WebhookHandlerError>> + Send>>;
+type Handler = Box<dyn Fn(WebhookEvent) -> HandlerFuture + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookHandlerError {
+    /// The event could not be processed right now, Razorpay should deliver
+    /// it again.
+    Retry(String),
+    /// The event can never be processed, Razorpay should not deliver it
+    /// again.
+    Discard(String),
+}
+
+impl Display for WebhookHandlerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        match self {
+            WebhookHandlerError::Retry(reason) => {
+                write!(f, "Retry: {}", reason)
+            }
+            WebhookHandlerError::Discard(reason) => {
+                write!(f, "Discard: {}", reason)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WebhookHandlerError {}
+
+/// The outcome of [`WebhookRouter::dispatch`].
+#[derive(Debug)]
+pub enum WebhookDispatch {
+    Handled(EventType),
+    /// The event was already processed by an earlier delivery.
+    Duplicate(EventType),
+    /// An earlier delivery of the event is still being processed.
+    InProgress(EventType),
+    /// No handler is registered for the event.
+    Unhandled(EventType),
+    /// The signature, age or body of the request is invalid.
+    Rejected(WebhookError),
+    Failed {
+        event: EventType,
+        error: WebhookHandlerError,
+    },
+}
+
+impl WebhookDispatch {
+    /// The status to respond to Razorpay with, Razorpay delivers the event
+    /// again for any status other than `2xx`.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            WebhookDispatch::Handled(_)
+            | WebhookDispatch::Duplicate(_)
+            | WebhookDispatch::Unhandled(_) => StatusCode::OK,
+            WebhookDispatch::InProgress(_) => StatusCode::CONFLICT,
+            WebhookDispatch::Rejected(_) => StatusCode::BAD_REQUEST,
+            WebhookDispatch::Failed { error, .. } => match error {
+                WebhookHandlerError::Retry(_) => {
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+                WebhookHandlerError::Discard(_) => StatusCode::OK,
+            },
+        }
+    }
+}
+
+enum Route {
+    Event(EventType),
+    Family(String),
+}
+
+/// Verifies webhook requests and dispatches the events to the handler
+/// registered for their [`EventType`] or event family.
+///
+/// A handler registered for an event takes precedence over one registered
+/// for its family, otherwise the first matching family wins.
+pub struct WebhookRouter {
+    secret: String,
+    max_age: Option<Duration>,
+    routes: Vec<(Route, Handler)>,
+    #[cfg(feature = "std")]
+    store: Option<Box<dyn WebhookEventStore>>,
+}
+
+impl Debug for WebhookRouter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatterResult {
+        let mut debug = f.debug_struct("WebhookRouter");
+        debug
+            .field("max_age", &self.max_age)
+            .field("routes", &self.routes.len());
+        #[cfg(feature = "std")]
+        debug.field("store", &self.store.is_some());
+        debug.finish_non_exhaustive()
+    }
+}
+
+fn event_name(event: &EventType) -> String {
+    match serde_json::to_value(event) {
+        Ok(Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn boxed<F, Fut>(handler: F) -> Handler
+where
+    F: Fn(WebhookEvent) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), WebhookHandlerError>> + Send + 'static,
+{
+    Box::new(move |event| Box::pin(handler(event)))
+}
+
+impl WebhookRouter {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            max_age: None,
+            routes: Vec::new(),
+            #[cfg(feature = "std")]
+            store: None,
+        }
+    }
+
+    /// Reject events created more than `max_age` ago.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Skip events whose id was already processed according to `store`.
+    /// An id is claimed before its handler runs and only recorded as done
+    /// once the handler returns, ids of events failing with
+    /// [`WebhookHandlerError::Retry`] are released again.
+    #[cfg(feature = "std")]
+    pub fn event_store(
+        mut self,
+        store: impl WebhookEventStore + 'static,
+    ) -> Self {
+        self.store = Some(Box::new(store));
+        self
+    }
+
+    pub fn on<F, Fut>(mut self, event: EventType, handler: F) -> Self
+    where
+        F: Fn(WebhookEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), WebhookHandlerError>> + Send + 'static,
+    {
+        self.routes.push((Route::Event(event), boxed(handler)));
+        self
+    }
+
+    /// Handle every event whose name starts with `family`, e.g.
+    /// `payment.dispute` or `payment.dispute.*` for all dispute events.
+    pub fn on_family<F, Fut>(mut self, family: &str, handler: F) -> Self
+    where
+        F: Fn(WebhookEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), WebhookHandlerError>> + Send + 'static,
+    {
+        let family = family.trim_end_matches('*').trim_end_matches('.');
+        self.routes
+            .push((Route::Family(family.to_owned()), boxed(handler)));
+        self
+    }
+
+    fn handler(&self, event: &EventType) -> Option<&Handler> {
+        let name = event_name(event);
+
+        let exact = self.routes.iter().find(|(route, _)| match route {
+            Route::Event(type_) => type_ == event,
+            Route::Family(_) => false,
+        });
+
+        let family = || {
+            self.routes.iter().find(|(route, _)| match route {
+                Route::Event(_) => false,
+                Route::Family(family) => {
+                    name.strip_prefix(family.as_str()).map_or(false, |rest| {
+                        rest.is_empty() || rest.starts_with('.')
+                    })
+                }
+            })
+        };
+
+        exact.or_else(family).map(|(_, handler)| handler)
+    }
+
+    /// Verifies the raw request body against the `X-Razorpay-Signature`
+    /// header and runs the matching handler, unless the
+    /// `X-Razorpay-Event-Id` was already processed according to the event
+    /// store.
+    pub async fn dispatch(
+        &self,
+        payload: &[u8],
+        signature: &str,
+        event_id: Option<&str>,
+    ) -> WebhookDispatch {
+        let event = match Webhook::construct_event_from_bytes(
+            payload,
+            signature,
+            &self.secret,
+            self.max_age,
+        ) {
+            Ok(event) => event,
+            Err(error) => return WebhookDispatch::Rejected(error),
+        };
+
+        let type_ = event.type_.clone();
+        let handler = match self.handler(&type_) {
+            Some(handler) => handler,
+            None => return WebhookDispatch::Unhandled(type_),
+        };
+
+        #[cfg(feature = "std")]
+        let event_id = match (&self.store, event_id) {
+            (Some(store), Some(event_id)) => {
+                match store.claim(event_id).await {
+                    Ok(WebhookEventClaim::Claimed) => Some((store, event_id)),
+                    Ok(WebhookEventClaim::InProgress) => {
+                        return WebhookDispatch::InProgress(type_)
+                    }
+                    Ok(WebhookEventClaim::Done) => {
+                        return WebhookDispatch::Duplicate(type_)
+                    }
+                    Err(error) => {
+                        return WebhookDispatch::Failed {
+                            event: type_,
+                            error: WebhookHandlerError::Retry(
+                                error.to_string(),
+                            ),
+                        }
+                    }
+                }
+            }
+            _ => None,
+        };
+        #[cfg(not(feature = "std"))]
+        let _ = event_id;
+
+        let result = handler(event).await;
+
+        // if recording the outcome fails the claim expires after the lease
+        // of the store, and a re-delivery is processed again
+        #[cfg(feature = "std")]
+        if let Some((store, event_id)) = event_id {
+            let _ = match &result {
+                Err(WebhookHandlerError::Retry(_)) => {
+                    store.release(event_id).await
+                }
+                _ => store.complete(event_id).await,
+            };
+        }
+
+        match result {
+            Ok(()) => WebhookDispatch::Handled(type_),
+            Err(error) => WebhookDispatch::Failed {
+                event: type_,
+                error,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{ready, Ready},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::{
+        util::generate_webhook_signature, webhook_store::MemoryEventStore,
+    };
+
+    const SECRET: &str = "webhook_secret";
+
+    fn signed(event: &str) -> (String, String) {
+        let payload = format!(
+            r#"{{"entity":"event","account_id":"acc_1","event":"{}","contains":[],"payload":{{}},"created_at":{}}}"#,
+            event,
+            Utc::now().timestamp()
+        );
+        let signature = generate_webhook_signature(&payload, SECRET);
+        (payload, signature)
+    }
+
+    async fn dispatch(router: &WebhookRouter, event: &str) -> WebhookDispatch {
+        let (payload, signature) = signed(event);
+        router
+            .dispatch(payload.as_bytes(), &signature, Some("ev_1"))
+            .await
+    }
+
+    #[tokio::test]
+    async fn records_event_only_after_handler_succeeds() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        let router = WebhookRouter::new(SECRET)
+            .event_store(MemoryEventStore::new(10, Duration::from_secs(60)))
+            .on(EventType::PaymentCaptured, move |_| {
+                let call = handler_calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match call {
+                        0 => Err(WebhookHandlerError::Retry("down".into())),
+                        _ => Ok(()),
+                    }
+                }
+            });
+
+        let retried = dispatch(&router, "payment.captured").await;
+        assert_eq!(retried.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let handled = dispatch(&router, "payment.captured").await;
+        assert!(matches!(handled, WebhookDispatch::Handled(_)));
+
+        let duplicate = dispatch(&router, "payment.captured").await;
+        assert!(matches!(duplicate, WebhookDispatch::Duplicate(_)));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn discarded_event_is_not_processed_again() {
+        let router = WebhookRouter::new(SECRET)
+            .event_store(MemoryEventStore::new(10, Duration::from_secs(60)))
+            .on(EventType::PaymentFailed, |_| async {
+                Err(WebhookHandlerError::Discard("unknown order".into()))
+            });
+
+        let discarded = dispatch(&router, "payment.failed").await;
+        assert_eq!(discarded.status_code(), StatusCode::OK);
+
+        let duplicate = dispatch(&router, "payment.failed").await;
+        assert!(matches!(duplicate, WebhookDispatch::Duplicate(_)));
+    }
+
+    // a handler discarding every event with `label`, telling the handlers
+    // apart in `routed_to`
+    fn labelled(
+        label: &'static str,
+    ) -> impl Fn(WebhookEvent) -> Ready<Result<(), WebhookHandlerError>> {
+        move |_| ready(Err(WebhookHandlerError::Discard(label.into())))
+    }
+
+    async fn routed_to(router: &WebhookRouter, event: &str) -> Option<String> {
+        match dispatch(router, event).await {
+            WebhookDispatch::Failed {
+                error: WebhookHandlerError::Discard(label),
+                ..
+            } => Some(label),
+            WebhookDispatch::Unhandled(_) => None,
+            dispatch => panic!("unexpected dispatch {:?}", dispatch),
+        }
+    }
+
+    #[tokio::test]
+    async fn matches_families_on_whole_segments() {
+        let router = WebhookRouter::new(SECRET)
+            .on_family("payment.dispute.*", labelled("dispute"))
+            .on_family("invoice.pa", labelled("invoice.pa"))
+            .on_family("order.paid", labelled("order"))
+            .on_family("subscription.", labelled("subscription"));
+
+        assert_eq!(
+            routed_to(&router, "payment.dispute.won").await.as_deref(),
+            Some("dispute")
+        );
+        assert_eq!(
+            routed_to(&router, "payment.dispute.action_required")
+                .await
+                .as_deref(),
+            Some("dispute")
+        );
+        assert_eq!(
+            routed_to(&router, "order.paid").await.as_deref(),
+            Some("order")
+        );
+        assert_eq!(
+            routed_to(&router, "subscription.halted").await.as_deref(),
+            Some("subscription")
+        );
+        assert_eq!(routed_to(&router, "invoice.paid").await, None);
+        assert_eq!(routed_to(&router, "payment.captured").await, None);
+    }
+
+    #[tokio::test]
+    async fn prefers_events_then_the_first_family() {
+        let router = WebhookRouter::new(SECRET)
+            .on_family("payment", labelled("payment"))
+            .on_family("payment.dispute", labelled("dispute"))
+            .on(EventType::PaymentDisputeLost, labelled("lost"));
+
+        assert_eq!(
+            routed_to(&router, "payment.dispute.lost").await.as_deref(),
+            Some("lost")
+        );
+        assert_eq!(
+            routed_to(&router, "payment.dispute.won").await.as_deref(),
+            Some("payment")
+        );
+        assert_eq!(
+            routed_to(&router, "payment.captured").await.as_deref(),
+            Some("payment")
+        );
+    }
+}
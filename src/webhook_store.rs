@@ -0,0 +1,475 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    future::{ready, Future},
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::Mutex as AsyncMutex,
+};
+
+/// How long a claimed event is considered in progress if it is neither
+/// completed nor released, e.g. because the process crashed while handling
+/// it.
+pub const DEFAULT_LEASE: Duration = Duration::from_secs(5 * 60);
+
+pub type StoreFuture<'a, T> =
+    Pin<Box<dyn Future<Output = io::Result<T>> + Send + 'a>>;
+
+/// The state of an event id when a delivery of the event is claimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEventClaim {
+    /// The event is new, or an earlier claim on it expired, and is now in
+    /// progress.
+    Claimed,
+    /// Another delivery of the event is being processed.
+    InProgress,
+    /// The event was already processed.
+    Done,
+}
+
+/// Remembers the ids of webhook events, sent by Razorpay in the
+/// `X-Razorpay-Event-Id` header, so that re-deliveries of processed events
+/// can be skipped.
+///
+/// An id is first claimed as in progress and only recorded as done once the
+/// event was processed. A claim that is neither completed nor released
+/// within the lease of the store expires, so an event whose processing was
+/// interrupted is processed again when Razorpay re-delivers it.
+pub trait WebhookEventStore: Send + Sync {
+    /// Claims `event_id` as in progress, unless it is in progress or done.
+    fn claim<'a>(
+        &'a self,
+        event_id: &'a str,
+    ) -> StoreFuture<'a, WebhookEventClaim>;
+
+    /// Records a claimed `event_id` as done.
+    fn complete<'a>(&'a self, event_id: &'a str) -> StoreFuture<'a, ()>;
+
+    /// Drops the claim on `event_id`, e.g. because processing it failed and
+    /// it should be handled again when Razorpay re-delivers it.
+    fn release<'a>(&'a self, event_id: &'a str) -> StoreFuture<'a, ()>;
+}
+
+fn poisoned<T>(_: T) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "event store lock poisoned")
+}
+
+/// The ids in progress along with when they were claimed.
+#[derive(Debug)]
+struct Claims {
+    lease: Duration,
+    in_progress: HashMap<String, Instant>,
+}
+
+impl Claims {
+    fn new() -> Self {
+        Self {
+            lease: DEFAULT_LEASE,
+            in_progress: HashMap::new(),
+        }
+    }
+
+    /// Returns `false` if `event_id` is claimed and its claim hasn't expired.
+    fn claim(&mut self, event_id: &str, now: Instant) -> bool {
+        let lease = self.lease;
+        self.in_progress
+            .retain(|_, claimed_at| now.duration_since(*claimed_at) < lease);
+
+        if self.in_progress.contains_key(event_id) {
+            return false;
+        }
+
+        self.in_progress.insert(event_id.to_owned(), now);
+        true
+    }
+
+    fn release(&mut self, event_id: &str) {
+        self.in_progress.remove(event_id);
+    }
+}
+
+#[derive(Debug)]
+struct MemoryEntries {
+    done: HashMap<String, Instant>,
+    order: VecDeque<(String, Instant)>,
+    claims: Claims,
+}
+
+/// An in-memory store keeping at most `capacity` processed ids for up to
+/// `ttl`, evicting the oldest ones first.
+#[derive(Debug)]
+pub struct MemoryEventStore {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<MemoryEntries>,
+}
+
+impl MemoryEventStore {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: Mutex::new(MemoryEntries {
+                done: HashMap::new(),
+                order: VecDeque::new(),
+                claims: Claims::new(),
+            }),
+        }
+    }
+
+    /// How long a claim lasts, [`DEFAULT_LEASE`] by default.
+    pub fn lease(mut self, lease: Duration) -> Self {
+        if let Ok(entries) = self.entries.get_mut() {
+            entries.claims.lease = lease;
+        }
+        self
+    }
+
+    fn claim_now(&self, event_id: &str) -> io::Result<WebhookEventClaim> {
+        let mut entries = self.entries.lock().map_err(poisoned)?;
+        let now = Instant::now();
+
+        while let Some((id, done_at)) = entries.order.front().cloned() {
+            if entries.order.len() < self.capacity
+                && now.duration_since(done_at) < self.ttl
+            {
+                break;
+            }
+
+            entries.order.pop_front();
+            // the id may have been done again since
+            if entries.done.get(&id) == Some(&done_at) {
+                entries.done.remove(&id);
+            }
+        }
+
+        if entries.done.contains_key(event_id) {
+            return Ok(WebhookEventClaim::Done);
+        }
+
+        Ok(if entries.claims.claim(event_id, now) {
+            WebhookEventClaim::Claimed
+        } else {
+            WebhookEventClaim::InProgress
+        })
+    }
+
+    fn complete_now(&self, event_id: &str) -> io::Result<()> {
+        let mut entries = self.entries.lock().map_err(poisoned)?;
+        let now = Instant::now();
+
+        entries.claims.release(event_id);
+        entries.done.insert(event_id.to_owned(), now);
+        entries.order.push_back((event_id.to_owned(), now));
+        Ok(())
+    }
+
+    fn release_now(&self, event_id: &str) -> io::Result<()> {
+        self.entries
+            .lock()
+            .map_err(poisoned)?
+            .claims
+            .release(event_id);
+        Ok(())
+    }
+}
+
+impl WebhookEventStore for MemoryEventStore {
+    fn claim<'a>(
+        &'a self,
+        event_id: &'a str,
+    ) -> StoreFuture<'a, WebhookEventClaim> {
+        Box::pin(ready(self.claim_now(event_id)))
+    }
+
+    fn complete<'a>(&'a self, event_id: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(ready(self.complete_now(event_id)))
+    }
+
+    fn release<'a>(&'a self, event_id: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(ready(self.release_now(event_id)))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[derive(Debug)]
+struct FileEntries {
+    file: File,
+    /// The processed ids along with when they were processed, in seconds
+    /// since the Unix epoch.
+    done: HashMap<String, u64>,
+    /// The lines in the file, including those of expired ids.
+    lines: usize,
+    claims: Claims,
+}
+
+/// A store persisting processed ids to a file, one `<timestamp> <id>` line
+/// per id, and keeping claims in memory only.
+///
+/// Ids processed more than `ttl` ago are forgotten, the file is rewritten
+/// without them when it is opened and whenever more than half of its lines
+/// have expired.
+#[derive(Debug)]
+pub struct FileEventStore {
+    path: PathBuf,
+    ttl: Duration,
+    entries: AsyncMutex<FileEntries>,
+}
+
+impl FileEventStore {
+    pub async fn open(
+        path: impl AsRef<Path>,
+        ttl: Duration,
+    ) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let content = match fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                String::new()
+            }
+            Err(error) => return Err(error),
+        };
+
+        let mut done = HashMap::new();
+        for line in content.lines() {
+            // lines not written by this store are dropped on compaction
+            if let Some((done_at, id)) = line.split_once(' ') {
+                if let Ok(done_at) = done_at.parse::<u64>() {
+                    done.insert(id.to_owned(), done_at);
+                }
+            }
+        }
+
+        let mut entries = FileEntries {
+            file: Self::append(&path).await?,
+            done,
+            lines: 0,
+            claims: Claims::new(),
+        };
+        Self::compact(&path, ttl, &mut entries).await?;
+
+        Ok(Self {
+            path,
+            ttl,
+            entries: AsyncMutex::new(entries),
+        })
+    }
+
+    /// How long a claim lasts, [`DEFAULT_LEASE`] by default.
+    pub fn lease(mut self, lease: Duration) -> Self {
+        self.entries.get_mut().claims.lease = lease;
+        self
+    }
+
+    async fn append(path: &Path) -> io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+    }
+
+    fn is_expired(ttl: Duration, done_at: u64, now: u64) -> bool {
+        now.saturating_sub(done_at) >= ttl.as_secs()
+    }
+
+    /// Rewrites the file with the ids that haven't expired, through a
+    /// temporary file so that a crash can't lose the ids.
+    async fn compact(
+        path: &Path,
+        ttl: Duration,
+        entries: &mut FileEntries,
+    ) -> io::Result<()> {
+        let now = unix_now();
+        entries
+            .done
+            .retain(|_, done_at| !Self::is_expired(ttl, *done_at, now));
+
+        let mut content = String::new();
+        for (id, done_at) in &entries.done {
+            content.push_str(&format!("{} {}\n", done_at, id));
+        }
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let mut tmp = File::create(&tmp_path).await?;
+        tmp.write_all(content.as_bytes()).await?;
+        tmp.sync_all().await?;
+        drop(tmp);
+        fs::rename(&tmp_path, path).await?;
+
+        entries.file = Self::append(path).await?;
+        entries.lines = entries.done.len();
+        Ok(())
+    }
+
+    async fn complete_now(&self, event_id: &str) -> io::Result<()> {
+        if event_id.contains(['\n', '\r']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "event id contains a line break",
+            ));
+        }
+
+        let mut entries = self.entries.lock().await;
+        let done_at = unix_now();
+
+        entries
+            .file
+            .write_all(format!("{} {}\n", done_at, event_id).as_bytes())
+            .await?;
+        entries.file.sync_data().await?;
+        entries.lines += 1;
+        entries.done.insert(event_id.to_owned(), done_at);
+        entries.claims.release(event_id);
+
+        let expired = entries
+            .done
+            .values()
+            .filter(|done_at| Self::is_expired(self.ttl, **done_at, unix_now()))
+            .count();
+        if entries.lines > 2 * (entries.done.len() - expired) {
+            Self::compact(&self.path, self.ttl, &mut entries).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl WebhookEventStore for FileEventStore {
+    fn claim<'a>(
+        &'a self,
+        event_id: &'a str,
+    ) -> StoreFuture<'a, WebhookEventClaim> {
+        Box::pin(async move {
+            let mut entries = self.entries.lock().await;
+
+            if let Some(done_at) = entries.done.get(event_id) {
+                if !Self::is_expired(self.ttl, *done_at, unix_now()) {
+                    return Ok(WebhookEventClaim::Done);
+                }
+            }
+
+            Ok(if entries.claims.claim(event_id, Instant::now()) {
+                WebhookEventClaim::Claimed
+            } else {
+                WebhookEventClaim::InProgress
+            })
+        })
+    }
+
+    fn complete<'a>(&'a self, event_id: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(self.complete_now(event_id))
+    }
+
+    fn release<'a>(&'a self, event_id: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            self.entries.lock().await.claims.release(event_id);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn memory_store_records_done_only_after_complete() {
+        let store = MemoryEventStore::new(10, Duration::from_secs(60));
+
+        assert_eq!(
+            store.claim("ev_1").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+        assert_eq!(
+            store.claim("ev_1").await.unwrap(),
+            WebhookEventClaim::InProgress
+        );
+
+        store.release("ev_1").await.unwrap();
+        assert_eq!(
+            store.claim("ev_1").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+
+        store.complete("ev_1").await.unwrap();
+        assert_eq!(store.claim("ev_1").await.unwrap(), WebhookEventClaim::Done);
+    }
+
+    #[tokio::test]
+    async fn memory_store_expires_claims_and_evicts_oldest() {
+        let store = MemoryEventStore::new(1, Duration::from_secs(60))
+            .lease(Duration::ZERO);
+
+        assert_eq!(
+            store.claim("ev_1").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+        // an unfinished claim doesn't block a later delivery past its lease
+        assert_eq!(
+            store.claim("ev_1").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+
+        store.complete("ev_1").await.unwrap();
+        store.claim("ev_2").await.unwrap();
+        store.complete("ev_2").await.unwrap();
+        assert_eq!(
+            store.claim("ev_1").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+    }
+
+    #[tokio::test]
+    async fn file_store_persists_done_ids_only() {
+        let path = std::env::temp_dir()
+            .join(format!("rusty-razorpay-events-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = FileEventStore::open(&path, Duration::from_secs(60))
+            .await
+            .unwrap();
+        store.claim("ev_1").await.unwrap();
+        store.complete("ev_1").await.unwrap();
+        // claimed but never completed, as if the process crashed
+        store.claim("ev_2").await.unwrap();
+        drop(store);
+
+        let store = FileEventStore::open(&path, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(store.claim("ev_1").await.unwrap(), WebhookEventClaim::Done);
+        assert_eq!(
+            store.claim("ev_2").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+        assert!(store.complete("ev\n3").await.is_err());
+        drop(store);
+
+        // expired ids are dropped from the file when it is opened
+        std::fs::write(&path, "1 ev_old\nnot a line\n").unwrap();
+        let store = FileEventStore::open(&path, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.claim("ev_old").await.unwrap(),
+            WebhookEventClaim::Claimed
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+
+        let _ = std::fs::remove_file(&path);
+    }
+}